@@ -0,0 +1,47 @@
+//! Compares `Nuban::from_bytes` with `Nuban::new` on the same accounts. Run
+//! with `cargo bench --bench from_bytes`.
+
+use nuban::Nuban;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 20;
+
+fn time(mut validate: impl FnMut() -> usize, expected: usize) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            assert_eq!(black_box(validate()), expected);
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let inputs: Vec<String> = (0..100_000u32)
+        .map(|serial| Nuban::account_number_for("058", &format!("{:09}", serial)).unwrap())
+        .collect();
+
+    let new = time(
+        || {
+            inputs
+                .iter()
+                .filter(|a| Nuban::new(black_box("058"), a).is_ok())
+                .count()
+        },
+        inputs.len(),
+    );
+    let from_bytes = time(
+        || {
+            inputs
+                .iter()
+                .filter(|a| Nuban::from_bytes(black_box(b"058"), a.as_bytes()).is_ok())
+                .count()
+        },
+        inputs.len(),
+    );
+
+    println!("new:        {:?}", new);
+    println!("from_bytes: {:?}", from_bytes);
+}
@@ -0,0 +1,64 @@
+//! C ABI for validating NUBANs from other languages.
+//!
+//! The crate builds as an rlib only, so build the shared library (e.g.
+//! `libnuban.so`, `libnuban.dylib` or `nuban.dll`) for Python's `ctypes`,
+//! Node's `ffi-napi` or Go's cgo with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+
+#![allow(unsafe_code)]
+
+use crate::Nuban;
+use core::ffi::{c_char, CStr};
+
+/// Returns `1` if the account is valid, `0` if it is not, and `-1` if
+/// either argument is null or not valid UTF-8.
+///
+/// # Safety
+///
+/// Non-null arguments must point to NUL-terminated strings that stay valid
+/// for the duration of the call. The caller keeps ownership of both
+/// strings; they are never freed or retained here.
+#[no_mangle]
+pub unsafe extern "C" fn nuban_is_valid(
+    bank_code: *const c_char,
+    account_number: *const c_char,
+) -> i32 {
+    match (to_str(bank_code), to_str(account_number)) {
+        (Some(bank_code), Some(account_number)) => {
+            Nuban::is_valid(bank_code, account_number) as i32
+        }
+        _ => -1,
+    }
+}
+
+unsafe fn to_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{ffi::CString, ptr};
+
+    #[test]
+    fn test_nuban_is_valid() {
+        let bank_code = CString::new("058").unwrap();
+        let valid = CString::new("0152792740").unwrap();
+        let invalid = CString::new("0152792741").unwrap();
+        let non_utf8 = CString::new(vec![0xff, 0xfe]).unwrap();
+
+        unsafe {
+            assert_eq!(nuban_is_valid(bank_code.as_ptr(), valid.as_ptr()), 1);
+            assert_eq!(nuban_is_valid(bank_code.as_ptr(), invalid.as_ptr()), 0);
+            assert_eq!(nuban_is_valid(ptr::null(), valid.as_ptr()), -1);
+            assert_eq!(nuban_is_valid(bank_code.as_ptr(), ptr::null()), -1);
+            assert_eq!(nuban_is_valid(bank_code.as_ptr(), non_utf8.as_ptr()), -1);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Streaming validation of `bank_code,account_number` CSV data.
+//!
+//! This is deliberately not a general CSV parser: fields are split on
+//! commas and trimmed, with no support for quoting.
+
+use crate::{Error, OwnedNuban};
+use std::io::BufRead;
+
+/// Validates each row of `reader`, yielding its 1-based line number and
+/// result.
+///
+/// A first line without any digits, like `bank_code,account_number`, is
+/// taken to be a header and skipped, as are blank lines. Rows without
+/// exactly two columns yield `Error::InvalidRecord`. A read error is yielded
+/// as `Error::InvalidRecord` for the line it happened on, and ends the
+/// iteration.
+pub fn validate_reader<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = (usize, Result<OwnedNuban, Error>)> {
+    let mut failed = false;
+    reader
+        .lines()
+        .enumerate()
+        .map_while(move |(index, line)| {
+            if failed {
+                return None;
+            }
+            let line_number = index + 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    failed = true;
+                    return Some(Some((
+                        line_number,
+                        Err(Error::InvalidRecord(err.to_string())),
+                    )));
+                }
+            };
+            if line.trim().is_empty() || (index == 0 && is_header(&line)) {
+                return Some(None);
+            }
+            Some(Some((line_number, validate_row(&line))))
+        })
+        .flatten()
+}
+
+fn is_header(line: &str) -> bool {
+    !line.bytes().any(|b| b.is_ascii_digit())
+}
+
+fn validate_row(line: &str) -> Result<OwnedNuban, Error> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    match fields[..] {
+        [bank_code, account_number] => OwnedNuban::new(bank_code.into(), account_number.into()),
+        _ => Err(Error::InvalidRecord(format!(
+            "expected 2 columns, found {}: {:?}",
+            fields.len(),
+            line
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccountError;
+    use std::io::{self, Cursor, Read};
+
+    #[test]
+    fn test_validate_reader() {
+        let csv = "bank_code,account_number\n058,0152792740\n\n058, 0152792741\n058\n";
+        let results: Vec<_> = validate_reader(Cursor::new(csv)).collect();
+        assert_eq!(results.len(), 3);
+
+        let (line, result) = &results[0];
+        assert_eq!(*line, 2);
+        assert_eq!(result.as_ref().unwrap().account_number(), "0152792740");
+
+        let (line, result) = &results[1];
+        assert_eq!(*line, 4);
+        assert_eq!(
+            result.as_ref().unwrap_err().reason(),
+            Some(AccountError::CheckDigitMismatch)
+        );
+
+        let (line, result) = &results[2];
+        assert_eq!(*line, 5);
+        assert!(matches!(result, Err(Error::InvalidRecord(_))));
+    }
+
+    #[test]
+    fn test_validate_reader_without_header() {
+        let csv = "058,0152792740\r\n057,2209514399,extra\r\n";
+        let lines: Vec<_> = validate_reader(Cursor::new(csv))
+            .map(|(line, result)| (line, result.is_ok()))
+            .collect();
+        assert_eq!(lines, vec![(1, true), (2, false)]);
+    }
+
+    #[test]
+    fn test_validate_reader_malformed_first_row() {
+        let csv = "05A,0152792740\n058,0152792740\n";
+        let results: Vec<_> = validate_reader(Cursor::new(csv)).collect();
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], (1, Err(Error::InvalidBankCode(_)))));
+        assert!(matches!(results[1], (2, Ok(_))));
+    }
+
+    #[test]
+    fn test_validate_reader_stops_on_read_error() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disconnected"))
+            }
+        }
+
+        let results: Vec<_> = validate_reader(io::BufReader::new(Failing)).collect();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], (1, Err(Error::InvalidRecord(_)))));
+    }
+}
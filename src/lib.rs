@@ -1,179 +1,3621 @@
 //! This is a lightweight crate for verifying NUBAN numbers
 //! for all Nigerian bank accounts as was directed by the CBN.
+//!
+//! The `std` feature is enabled by default. Without it the crate is
+//! `#![no_std]` (it still needs `alloc`), and bank lookups fall back to a
+//! linear scan of `BANKS` instead of a lazily built `HashMap`.
+//!
+//! Apart from the bindings behind the `ffi` and `wasm` features, the crate
+//! contains no `unsafe` code; the default build enforces this with
+//! `#![forbid(unsafe_code)]`.
 
-use std::{cell::Cell, collections::HashMap, fmt, sync::Once};
+#![cfg_attr(not(feature = "std"), no_std)]
+// The crate has no `unsafe` outside the bindings, which opt back in to it.
+// `forbid` can't be relaxed locally, so it's `deny` when they're built.
+#![cfg_attr(not(any(feature = "ffi", feature = "wasm")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "ffi", feature = "wasm"), deny(unsafe_code))]
 
-pub const BANKS: [(&'static str, &'static str); 24] = [
-    ("044", "Access Bank"),
+extern crate alloc;
+
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+// Validation steps are logged with the `logging` feature. Without it these
+// expand to dead code, so the arguments still type-check but cost nothing.
+#[cfg(feature = "logging")]
+macro_rules! debug {
+    ($($arg:tt)*) => { log::debug!($($arg)*) };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! debug {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
+#[cfg(feature = "logging")]
+macro_rules! trace {
+    ($($arg:tt)*) => { log::trace!($($arg)*) };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! trace {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
+
+/// Banks currently licensed by the CBN, keyed by their NUBAN bank code.
+/// Microfinance banks and other fintechs are listed under their 6-digit
+/// NIBSS institution codes.
+///
+/// Some codes were reassigned when banks merged or were taken over, e.g.
+/// `076` (formerly Skye Bank) now belongs to Polaris Bank and `082`
+/// (formerly BankPhb) to Keystone Bank.
+#[rustfmt::skip]
+pub const BANKS: [(&str, &str, BankCategory); 36] = [
+    ("044",    "Access Bank",                  BankCategory::Commercial),
+    ("063",    "Access Bank (Diamond)",        BankCategory::Commercial),
+    ("023",    "Citibank",                     BankCategory::Commercial),
+    ("559",    "Coronation Merchant Bank",     BankCategory::Merchant),
+    ("050",    "Ecobank",                      BankCategory::Commercial),
+    ("214",    "FCMB",                         BankCategory::Commercial),
+    ("070",    "Fidelity",                     BankCategory::Commercial),
+    ("011",    "First Bank",                   BankCategory::Commercial),
+    ("501",    "FSDH Merchant Bank",           BankCategory::Merchant),
+    ("103",    "Globus Bank",                  BankCategory::Commercial),
+    ("058",    "Guaranty Trust Bank",          BankCategory::Commercial),
+    ("301",    "Jaiz Bank",                    BankCategory::NonInterest),
+    ("082",    "Keystone Bank",                BankCategory::Commercial),
+    ("303",    "Lotus Bank",                   BankCategory::NonInterest),
+    ("104",    "Parallex Bank",                BankCategory::Commercial),
+    ("076",    "Polaris Bank",                 BankCategory::Commercial),
+    ("105",    "PremiumTrust Bank",            BankCategory::Commercial),
+    ("101",    "Providus Bank",                BankCategory::Commercial),
+    ("502",    "Rand Merchant Bank",           BankCategory::Merchant),
+    ("221",    "StanbicIBTC",                  BankCategory::Commercial),
+    ("068",    "Standard Chartered Bank",      BankCategory::Commercial),
+    ("232",    "Sterling Bank",                BankCategory::Commercial),
+    ("100",    "SunTrust Bank",                BankCategory::Commercial),
+    ("302",    "TAJ Bank",                     BankCategory::NonInterest),
+    ("102",    "Titan Trust Bank",             BankCategory::Commercial),
+    ("032",    "Union Bank",                   BankCategory::Commercial),
+    ("033",    "United Bank For Africa",       BankCategory::Commercial),
+    ("215",    "Unity Bank",                   BankCategory::Commercial),
+    ("035",    "Wema Bank",                    BankCategory::Commercial),
+    ("057",    "Zenith Bank",                  BankCategory::Commercial),
+    ("090267", "Kuda Microfinance Bank",       BankCategory::Microfinance),
+    ("090405", "Moniepoint Microfinance Bank", BankCategory::Microfinance),
+    ("100004", "OPay",                         BankCategory::MobileMoney),
+    ("100033", "PalmPay",                      BankCategory::MobileMoney),
+    ("120001", "9 Payment Service Bank",       BankCategory::PaymentService),
+    ("120003", "MoMo Payment Service Bank",    BankCategory::PaymentService),
+];
+
+/// The 6-digit NIBSS Instant Payment (NIP) institution code of each bank in
+/// `BANKS` with a 3-digit CBN code, as `(cbn_code, nip_code)`. Banks listed
+/// under a 6-digit code already use their NIP code.
+#[rustfmt::skip]
+pub const NIP_CODES: [(&str, &str); 30] = [
+    ("044", "000014"),
+    ("063", "000005"),
+    ("023", "000009"),
+    ("559", "060001"),
+    ("050", "000010"),
+    ("214", "000003"),
+    ("070", "000007"),
+    ("011", "000016"),
+    ("501", "060002"),
+    ("103", "000027"),
+    ("058", "000013"),
+    ("301", "000006"),
+    ("082", "000002"),
+    ("303", "000029"),
+    ("104", "000030"),
+    ("076", "000008"),
+    ("105", "000031"),
+    ("101", "000023"),
+    ("502", "000024"),
+    ("221", "000012"),
+    ("068", "000021"),
+    ("232", "000001"),
+    ("100", "000022"),
+    ("302", "000026"),
+    ("102", "000025"),
+    ("032", "000018"),
+    ("033", "000004"),
+    ("215", "000011"),
+    ("035", "000017"),
+    ("057", "000015"),
+];
+
+/// Banks that no longer operate, e.g. after a merger or the revocation of
+/// their licence. Their codes are not valid for new accounts, so they are
+/// kept apart from `BANKS` and only used to explain why a code is rejected.
+#[rustfmt::skip]
+pub const DEFUNCT_BANKS: [(&str, &str); 6] = [
     ("014", "Afribank"),
-    ("023", "Citibank"),
-    ("063", "Diamond Bank"),
-    ("050", "Ecobank"),
+    ("030", "Heritage Bank"),
     ("040", "Equitorial Trust Bank"),
-    ("011", "First Bank"),
-    ("214", "FCMB"),
-    ("070", "Fidelity"),
-    ("085", "FinBank"),
-    ("058", "Guaranty Trust Bank"),
-    ("069", "Intercontinentl Bank"),
     ("056", "Oceanic Bank"),
-    ("082", "BankPhb"),
-    ("076", "Skye Bank"),
-    ("084", "SpringBank"),
-    ("221", "StanbicIBTC"),
-    ("068", "Standard Chartered Bank"),
-    ("232", "Sterling Bank"),
-    ("033", "United Bank For Africa"),
-    ("032", "Union Bank"),
-    ("035", "Wema Bank"),
-    ("057", "Zenith Bank"),
-    ("215", "Unity Bank"),
+    ("069", "Intercontinental Bank"),
+    ("085", "FinBank"),
+];
+
+/// The code of the bank that took over each bank in `DEFUNCT_BANKS`, for
+/// `Nuban::bank_successor`. Codes still in `BANKS`, like Diamond Bank's
+/// `063` (now routed to Access Bank), are operational and aren't listed.
+#[rustfmt::skip]
+const BANK_SUCCESSORS: [(&str, &str); 5] = [
+    ("014", "076"), // Afribank, via Mainstreet and Skye Bank
+    ("040", "232"), // Equitorial Trust Bank
+    ("056", "050"), // Oceanic Bank
+    ("069", "044"), // Intercontinental Bank
+    ("085", "214"), // FinBank
 ];
 
-struct LazyBanks(Once, Cell<Option<HashMap<&'static str, &'static str>>>);
+/// Common abbreviations for `Nuban::resolve_bank_fuzzy`, keyed by their
+/// normalized form.
+const BANK_ALIASES: [(&str, &str); 8] = [
+    ("gt", "058"),
+    ("gtb", "058"),
+    ("gtbank", "058"),
+    ("gtco", "058"),
+    ("uba", "033"),
+    ("fbn", "011"),
+    ("firstbank", "011"),
+    ("stanbic", "221"),
+];
 
-unsafe impl Sync for LazyBanks {}
+/// The kind of institution a bank code belongs to, as licensed by the CBN.
+#[derive(Eq, Copy, Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BankCategory {
+    Commercial,
+    Merchant,
+    NonInterest,
+    Microfinance,
+    MobileMoney,
+    PaymentService,
+    /// Used for banks registered at runtime without a category.
+    Unknown,
+}
 
-static LAZY_BANKS: LazyBanks = LazyBanks(Once::new(), Cell::new(None));
+/// A bank's code, name and category together, as returned by `Nuban::bank`.
+#[derive(Eq, Copy, Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Bank<'a> {
+    pub code: &'a str,
+    pub name: &'a str,
+    pub category: BankCategory,
+}
 
-#[derive(Eq, Clone, Debug, PartialEq)]
+/// Everything known about a built-in bank, as returned by
+/// `Nuban::lookup_bank`.
+#[derive(Eq, Copy, Clone, Debug, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BankInfo {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub category: BankCategory,
+    /// `false` for banks in `DEFUNCT_BANKS`.
+    pub operational: bool,
+    /// See `Nuban::bank_successor`.
+    pub successor: Option<&'static str>,
+    /// See `Nuban::nip_code`.
+    pub nip_code: Option<&'static str>,
+}
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod prelude;
+pub mod resolve;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+static BANKS_MAP: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+#[cfg(feature = "std")]
+static CUSTOM_BANKS: OnceLock<RwLock<HashMap<&'static str, CustomBank>>> = OnceLock::new();
+
+#[cfg(feature = "std")]
+type CustomBank = (&'static str, BankCategory);
+
+/// How a bank's check digit is computed from its code and account serial.
+pub trait CheckDigitAlgorithm: fmt::Debug + Send + Sync {
+    /// Returns the check digit for a 9-digit `serial`, or `None` if accounts
+    /// at this bank have no check digit and are validated on format alone.
+    fn check_digit(&self, bank_code: &str, serial: &str) -> Option<u32>;
+}
+
+/// The CBN's NUBAN algorithm, used for every bank unless a `BankRegistry`
+/// says otherwise. Only 3-digit bank codes have a check digit, and there is
+/// none for a `serial` that isn't 9 ASCII digits.
+#[derive(Eq, Copy, Clone, Debug, Default, PartialEq)]
+pub struct CbnNuban;
+
+impl CheckDigitAlgorithm for CbnNuban {
+    fn check_digit(&self, bank_code: &str, serial: &str) -> Option<u32> {
+        // `calculate_check_digit` panics on non-digits, which a registered
+        // bank code could contain, and on a short serial.
+        if !has_check_digit(bank_code)
+            || !is_digits(bank_code)
+            || serial.len() != 9
+            || !is_digits(serial)
+        {
+            return None;
+        }
+        Some(Nuban::calculate_check_digit(
+            bank_code.as_bytes(),
+            serial.as_bytes(),
+        ))
+    }
+}
+
+/// A self-contained set of banks to validate against, for when the global
+/// list (and `Nuban::register_bank`) is too coarse, e.g. in isolated tests
+/// or multi-tenant services.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct BankRegistry {
+    banks: HashMap<String, String>,
+    algorithms: HashMap<String, Arc<dyn CheckDigitAlgorithm>>,
+}
+
+/// Registries are equal if they have the same banks and the same algorithm
+/// instances, since algorithms can't be compared themselves.
+#[cfg(feature = "std")]
+impl PartialEq for BankRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.banks == other.banks
+            && self.algorithms.len() == other.algorithms.len()
+            && self.algorithms.iter().all(|(code, algorithm)| {
+                other
+                    .algorithms
+                    .get(code)
+                    .is_some_and(|other| Arc::ptr_eq(algorithm, other))
+            })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for BankRegistry {}
+
+#[cfg(feature = "std")]
+impl BankRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a bank. Accounts are only accepted at codes made of ASCII
+    /// digits, so any other `code` is registered but never validates.
+    pub fn with_bank(mut self, code: impl Into<String>, name: impl Into<String>) -> Self {
+        self.banks.insert(code.into(), name.into());
+        self
+    }
+
+    /// Adds every built-in bank from `BANKS`.
+    pub fn with_defaults(self) -> Self {
+        BANKS.iter().fold(self, |registry, (code, name, _)| {
+            registry.with_bank(*code, *name)
+        })
+    }
+
+    pub fn is_valid_bank(&self, bank_code: &str) -> bool {
+        self.banks.contains_key(bank_code)
+    }
+
+    pub fn bank_name(&self, bank_code: &str) -> Option<&str> {
+        self.banks.get(bank_code).map(String::as_str)
+    }
+
+    /// Validates accounts at `code` with `algorithm` instead of `CbnNuban`.
+    pub fn with_algorithm(
+        mut self,
+        code: impl Into<String>,
+        algorithm: impl CheckDigitAlgorithm + 'static,
+    ) -> Self {
+        self.algorithms.insert(code.into(), Arc::new(algorithm));
+        self
+    }
+
+    /// The algorithm accounts at `bank_code` are validated with.
+    pub fn algorithm(&self, bank_code: &str) -> &dyn CheckDigitAlgorithm {
+        match self.algorithms.get(bank_code) {
+            Some(algorithm) => algorithm.as_ref(),
+            None => &CbnNuban,
+        }
+    }
+}
+
+/// `Nuban`s order by bank code, then by account number. They only borrow
+/// their input, so they are `Copy`.
+#[derive(Copy, Clone, Debug)]
 pub struct Nuban<'a>(&'a str, &'a str, &'a str);
 
-#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+// Equality, hashing and ordering only look at the bank code and account
+// number, so anything derived from them (like the check digit) can be
+// added or cached without changing which `Nuban`s are equal.
+impl Nuban<'_> {
+    fn key(&self) -> (&str, &str) {
+        (self.0, self.1)
+    }
+}
+
+impl PartialEq for Nuban<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Nuban<'_> {}
+
+impl core::hash::Hash for Nuban<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Ord for Nuban<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for Nuban<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Eq, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error {
-    InvalidBankCode,
-    InvalidAccountNumber,
+    /// The bank code, as given, is not a known bank.
+    InvalidBankCode(String),
+    /// The account number, as given, and why it was rejected.
+    InvalidAccountNumber { value: String, reason: AccountError },
+    /// A record of input that could not be read as a bank code and account
+    /// number, e.g. a CSV row with the wrong number of columns.
+    InvalidRecord(String),
+}
+
+/// Why an account number was rejected.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccountError {
+    WrongLength,
+    NonDigit,
+    CheckDigitMismatch,
+    /// The serial is a placeholder, such as all zeros; see `Nuban::new_strict`.
+    PlaceholderSerial,
+}
+
+/// The outcome of `Nuban::verify_against`.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerifyResult {
+    Match,
+    BankMismatch,
+    AccountMismatch,
+    /// The submitted account isn't a valid NUBAN at all.
+    SubmittedInvalid,
+}
+
+/// The outcome of `Nuban::classify`, in the order the checks are made.
+#[derive(Eq, Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Validity {
+    Valid,
+    UnknownBank,
+    WrongLength,
+    NonNumeric,
+    ChecksumFailed,
+}
+
+impl Error {
+    fn bank_code(value: &str) -> Self {
+        Error::InvalidBankCode(value.into())
+    }
+
+    fn account_number(value: &str, reason: AccountError) -> Self {
+        Error::InvalidAccountNumber {
+            value: value.into(),
+            reason,
+        }
+    }
+
+    pub fn is_bank_error(&self) -> bool {
+        matches!(self, Error::InvalidBankCode(_))
+    }
+
+    /// Returns `true` for any `InvalidAccountNumber`, whatever its reason.
+    pub fn is_account_error(&self) -> bool {
+        matches!(self, Error::InvalidAccountNumber { .. })
+    }
+
+    /// Why the account number was rejected, or `None` for bank code and
+    /// record errors.
+    pub fn reason(&self) -> Option<AccountError> {
+        match self {
+            Error::InvalidBankCode(_) | Error::InvalidRecord(_) => None,
+            Error::InvalidAccountNumber { reason, .. } => Some(*reason),
+        }
+    }
 }
 
 impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidBankCode(value) => write!(f, "invalid bank code {:?}", value),
+            Error::InvalidAccountNumber { value, reason } => {
+                write!(f, "invalid account number {:?}: {}", value, reason)
+            }
+            Error::InvalidRecord(message) => write!(f, "invalid record: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for AccountError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let reason = match self {
-            Error::InvalidBankCode => "invalid bank code",
-            Error::InvalidAccountNumber => "invalid account number",
+            AccountError::WrongLength => "wrong length",
+            AccountError::NonDigit => "contains a non-digit character",
+            AccountError::CheckDigitMismatch => "check digit mismatch",
+            AccountError::PlaceholderSerial => "serial is a placeholder",
         };
         write!(f, "{}", reason)
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Validation failures become `io::ErrorKind::InvalidData`, so they can be
+/// propagated with `?` alongside I/O errors when reading account data.
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+    }
+}
+
+/// Renders the full 13-digit NUBAN: bank code followed by account number.
+impl fmt::Display for Nuban<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.0, self.1)
+    }
+}
+
+/// Compares against the 13-digit form, as rendered by `Display`.
+impl PartialEq<str> for Nuban<'_> {
+    fn eq(&self, other: &str) -> bool {
+        other.len() == self.0.len() + self.1.len()
+            && other.starts_with(self.0)
+            && other.ends_with(self.1)
+    }
+}
+
+impl PartialEq<&str> for Nuban<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<Nuban<'_>> for str {
+    fn eq(&self, other: &Nuban<'_>) -> bool {
+        *other == *self
+    }
+}
+
+impl PartialEq<Nuban<'_>> for &str {
+    fn eq(&self, other: &Nuban<'_>) -> bool {
+        *other == **self
+    }
+}
+
+/// Converts a `(bank_code, account_number)` pair via `Nuban::new`.
+impl<'a> core::convert::TryFrom<(&'a str, &'a str)> for Nuban<'a> {
+    type Error = Error;
+
+    fn try_from((bank_code, account_number): (&'a str, &'a str)) -> Result<Self, Error> {
+        Nuban::new(bank_code, account_number)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Nuban<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Nuban", 2)?;
+        state.serialize_field("bank_code", self.bank_code())?;
+        state.serialize_field("account_number", self.account_number())?;
+        state.end()
+    }
+}
+
+/// Deserialized accounts go through `Nuban::new`, so invalid input fails
+/// to deserialize rather than producing an unchecked `Nuban`.
+#[cfg(feature = "serde")]
+impl<'de: 'a, 'a> serde::Deserialize<'de> for Nuban<'a> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Nuban")]
+        struct Fields<'a> {
+            bank_code: &'a str,
+            account_number: &'a str,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        Nuban::new(fields.bank_code, fields.account_number).map_err(serde::de::Error::custom)
+    }
+}
+
 impl<'a> Nuban<'a> {
     pub fn new(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        if !Self::is_valid_bank(bank_code) {
+            debug!("bank code {} not recognized", bank_code);
+            return Err(Error::bank_code(bank_code));
+        }
+        trace!("bank code {} recognized", bank_code);
+
+        let account = Self::with_known_bank(bank_code, account_number);
+        match &account {
+            Ok(_) => trace!("account number {} is valid", account_number),
+            Err(err) => debug!("{}", err),
+        }
+        account
+    }
+
+    /// Like `new`, but identifying the bank by its NIP institution code, e.g.
+    /// `"000013"` for Guaranty Trust Bank; see `NIP_CODES`.
+    pub fn from_nip_code(nip_code: &str, account_number: &'a str) -> Result<Self, Error> {
+        let bank_code = NIP_CODES
+            .iter()
+            .find(|(_, nip)| *nip == nip_code)
+            .map(|(code, _)| *code)
+            .or_else(|| {
+                Self::all_banks()
+                    .map(|(code, _)| code)
+                    .find(|code| code.len() == 6 && *code == nip_code)
+            })
+            .ok_or_else(|| Error::bank_code(nip_code))?;
+        Self::new(bank_code, account_number)
+    }
+
+    /// Like `new`, but validates the bank code against `registry` instead
+    /// of the global bank list.
+    #[cfg(feature = "std")]
+    pub fn new_with_registry(
+        registry: &BankRegistry,
+        bank_code: &'a str,
+        account_number: &'a str,
+    ) -> Result<Self, Error> {
+        #[rustfmt::skip] if !registry.is_valid_bank(bank_code) { Err(Error::bank_code(bank_code))? };
+        Self::with_algorithm(registry.algorithm(bank_code), bank_code, account_number)
+    }
+
+    /// Like `new`, but also rejecting serials that are a single repeated
+    /// digit, such as `"000000000"` or `"111111111"`. These pass the check
+    /// digit but are placeholders rather than real accounts.
+    pub fn new_strict(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        let nuban = Self::new(bank_code, account_number)?;
+        let first = nuban.1.as_bytes()[0];
+        #[rustfmt::skip] if nuban.serial().bytes().all(|b| b == first) { Err(Error::account_number(account_number, AccountError::PlaceholderSerial))? };
+        Ok(nuban)
+    }
+
+    /// Like `new`, but without comparing the check digit, for legacy data
+    /// whose check digits were never computed correctly. Use
+    /// `check_digit_matches` to tell whether the result is canonically valid.
+    pub fn new_lenient(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        #[rustfmt::skip] if !Self::is_valid_bank(bank_code) { Err(Error::bank_code(bank_code))? };
+        Self::well_formed(bank_code, account_number)
+    }
+
+    /// Like `new`, but ignoring whitespace around either input, e.g. from
+    /// spreadsheet cells. Whitespace inside them is still rejected.
+    pub fn new_trimmed(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        Self::new(bank_code.trim(), account_number.trim())
+    }
+
+    /// Like `new`, but accepting account numbers typed with separators, e.g.
+    /// `"0152-792-740"` or `"015 279 2740"`. Only whitespace, `-` and `.` are
+    /// stripped; any other non-digit is still rejected.
+    pub fn new_normalized(bank_code: &str, account_number: &str) -> Result<OwnedNuban, Error> {
+        let account_number = account_number
+            .chars()
+            .filter(|c| !(c.is_whitespace() || *c == '-' || *c == '.'))
+            .collect();
+        OwnedNuban::new(bank_code.into(), account_number)
+    }
+
+    /// Like `new`, but taking numbers, e.g. from integer database columns.
+    /// They are zero-padded to a 3-digit bank code and 10-digit account
+    /// number, so 6-digit bank codes can't be given this way.
+    pub fn from_numbers(bank_code: u16, account_number: u64) -> Result<OwnedNuban, Error> {
         #[rustfmt::skip] {
-            if !Self::is_valid_bank(bank_code) { Err(Error::InvalidBankCode)? }
-            if account_number.len() != 10  { Err(Error::InvalidAccountNumber)? }
+            if bank_code > 999                { Err(Error::bank_code(&format!("{}", bank_code)))? }
+            if account_number > 9_999_999_999 { Err(Error::account_number(&format!("{}", account_number), AccountError::WrongLength))? }
         };
 
-        let (_, check_digit) = Self::account_number_parts(bank_code, account_number)?;
-        Ok(Nuban(bank_code, account_number, check_digit))
+        OwnedNuban::new(
+            format!("{:03}", bank_code),
+            format!("{:010}", account_number),
+        )
+    }
+
+    fn with_known_bank(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        Self::with_algorithm(&CbnNuban, bank_code, account_number)
+    }
+
+    fn with_algorithm(
+        algorithm: &dyn CheckDigitAlgorithm,
+        bank_code: &'a str,
+        account_number: &'a str,
+    ) -> Result<Self, Error> {
+        let nuban = Self::well_formed(bank_code, account_number)?;
+        Self::verify_check_digit(algorithm, bank_code, account_number)?;
+        Ok(nuban)
+    }
+
+    // Every constructor goes through here, so a bank registered with a
+    // non-digit code never becomes a `Nuban`.
+    fn well_formed(bank_code: &'a str, account_number: &'a str) -> Result<Self, Error> {
+        #[rustfmt::skip] if !is_numeric_code(bank_code) { Err(Error::bank_code(bank_code))? };
+        check_account_bytes(account_number.as_bytes())
+            .map_err(|reason| Error::account_number(account_number, reason))?;
+        Ok(Nuban(bank_code, account_number, &account_number[9..]))
     }
 
-    fn account_number_parts(
+    /// Like `new`, but reporting every problem instead of stopping at the
+    /// first, e.g. both an unknown bank and a short account number. The
+    /// check digit is only compared once the rest is well-formed.
+    pub fn validate_collecting(
         bank_code: &'a str,
         account_number: &'a str,
-    ) -> Result<(&'a str, &'a str), Error> {
-        let (account_number, check_digit) = account_number.split_at(9);
-        match (
-            check_digit.chars().next().unwrap().to_digit(10),
-            Self::calculate_check_digit(bank_code, account_number),
-        ) {
-            (Some(l), r) if l != r => Err(Error::InvalidAccountNumber),
-            _ => Ok((account_number, check_digit)),
+    ) -> Result<Self, Vec<Error>> {
+        let mut errors = Vec::new();
+        if !Self::is_valid_bank(bank_code) {
+            errors.push(Error::bank_code(bank_code));
+        }
+        if account_number.len() != 10 {
+            errors.push(Error::account_number(
+                account_number,
+                AccountError::WrongLength,
+            ));
+        }
+        if !is_digits(account_number) {
+            errors.push(Error::account_number(
+                account_number,
+                AccountError::NonDigit,
+            ));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Self::with_known_bank(bank_code, account_number).map_err(|err| vec![err])
+    }
+
+    /// Like `new`, but taking the ASCII digits as bytes, e.g. straight from
+    /// a socket buffer. The input is checked as bytes, and only viewed as a
+    /// `&str` once it is known to be ASCII digits.
+    pub fn from_bytes(bank_code: &'a [u8], account_number: &'a [u8]) -> Result<Self, Error> {
+        let bank_code = Self::known_bank_code(bank_code)
+            .filter(|code| is_numeric_code(code))
+            .ok_or_else(|| Error::bank_code(&String::from_utf8_lossy(bank_code)))?;
+        let invalid =
+            |reason| Error::account_number(&String::from_utf8_lossy(account_number), reason);
+        check_account_bytes(account_number).map_err(invalid)?;
+        if has_check_digit(bank_code) && is_digits(bank_code) {
+            let expected = Self::calculate_check_digit(bank_code.as_bytes(), &account_number[..9]);
+            if u32::from(account_number[9] - b'0') != expected {
+                return Err(invalid(AccountError::CheckDigitMismatch));
+            }
+        }
+        // `check_account_bytes` only passes ASCII digits, which are UTF-8.
+        let account_number =
+            core::str::from_utf8(account_number).map_err(|_| invalid(AccountError::NonDigit))?;
+        Ok(Nuban(bank_code, account_number, &account_number[9..]))
+    }
+
+    /// The known bank code equal to `bank_code`, found without decoding it.
+    fn known_bank_code(bank_code: &[u8]) -> Option<&'static str> {
+        #[cfg(feature = "std")]
+        if let Some(code) = Self::custom_banks()
+            .keys()
+            .find(|code| code.as_bytes() == bank_code)
+        {
+            return Some(*code);
+        }
+        BANKS
+            .iter()
+            .map(|&(code, _, _)| code)
+            .find(|code| code.as_bytes() == bank_code)
+    }
+
+    /// Validates each `(bank_code, account_number)` pair, returning the
+    /// results in input order. See `ValidatedAccounts` to validate lazily.
+    pub fn validate_many(
+        pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Vec<Result<Self, Error>> {
+        pairs
+            .into_iter()
+            .map(|(bank_code, account_number)| Self::new(bank_code, account_number))
+            .collect()
+    }
+
+    /// Parses a combined 13-digit NUBAN, i.e. the 3-digit bank code
+    /// immediately followed by the 10-digit account number.
+    pub fn parse(nuban: &'a str) -> Result<Self, Error> {
+        #[rustfmt::skip] if nuban.len() != 13 { Err(Error::account_number(nuban, AccountError::WrongLength))? };
+
+        let bank_code = nuban.get(..3).ok_or_else(|| Error::bank_code(nuban))?;
+        let account_number = &nuban[3..];
+        #[rustfmt::skip] {
+            if !is_digits(bank_code) { Err(Error::bank_code(bank_code))? }
+            if !is_digits(account_number) { Err(Error::account_number(account_number, AccountError::NonDigit))? }
+        };
+
+        Self::new(bank_code, account_number)
+    }
+
+    /// Parses each line of `input` with `parse`, after trimming it, paired
+    /// with its 1-based line number. Blank lines are skipped.
+    pub fn parse_many(input: &str) -> Vec<(usize, Result<OwnedNuban, Error>)> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(line_number, line)| (line_number, Nuban::parse(line).map(|n| n.to_owned())))
+            .collect()
+    }
+
+    /// Parses a payload made by `to_qr_payload`. The bank name is only
+    /// informational and isn't checked, so payloads stay readable after a
+    /// bank is renamed.
+    pub fn from_qr_payload(payload: &'a str) -> Result<Self, Error> {
+        let fields: Vec<&str> = payload.split('|').collect();
+        match fields[..] {
+            ["NUBAN", bank_code, account_number, _] => Self::new(bank_code, account_number),
+            _ => Err(Error::InvalidRecord(format!(
+                "expected \"NUBAN|<bank code>|<account number>|<bank name>\", found {:?}",
+                payload
+            ))),
+        }
+    }
+
+    /// Checks a combined 13-digit NUBAN, as accepted by `parse`, without
+    /// building a `Nuban`.
+    pub fn validate_str(nuban: &str) -> Result<(), Error> {
+        Nuban::parse(nuban).map(|_| ())
+    }
+
+    /// Checks the format and check digit of an account at any well-formed
+    /// 3-digit bank code, whether or not it is a known bank, e.g. for offline
+    /// verification when the bank list may be out of date.
+    pub fn validate_checksum_only(bank_code: &str, account_number: &str) -> Result<(), Error> {
+        #[rustfmt::skip] if !Self::is_well_formed_bank_code(bank_code) { Err(Error::bank_code(bank_code))? };
+        Nuban::well_formed(bank_code, account_number)?;
+        Self::verify_check_digit(&CbnNuban, bank_code, account_number)
+    }
+
+    /// Checks a bank code and account number without building a `Nuban`,
+    /// e.g. from a `validator`-style custom field validation hook.
+    pub fn validate_field(bank_code: &str, account_number: &str) -> Result<(), Error> {
+        Nuban::new(bank_code, account_number).map(|_| ())
+    }
+
+    fn verify_check_digit(
+        algorithm: &dyn CheckDigitAlgorithm,
+        bank_code: &str,
+        account_number: &str,
+    ) -> Result<(), Error> {
+        let (serial, check_digit) = account_number.split_at(9);
+        let expected = match algorithm.check_digit(bank_code, serial) {
+            Some(expected) => expected,
+            None => return Ok(()),
+        };
+        match check_digit.chars().next().and_then(|c| c.to_digit(10)) {
+            Some(actual) if actual == expected => Ok(()),
+            _ => {
+                debug!("check digit expected {}, got {}", expected, check_digit);
+                Err(Error::account_number(
+                    account_number,
+                    AccountError::CheckDigitMismatch,
+                ))
+            }
         }
     }
 
+    /// Looks up the name of each of `codes`, in order, with `None` for
+    /// unknown codes.
+    pub fn bank_names(codes: &[&str]) -> Vec<Option<&'static str>> {
+        codes
+            .iter()
+            .map(|code| Self::lookup_bank_name(code))
+            .collect()
+    }
+
+    /// Returns `true` if `bank_code` is a known bank. Codes are not limited
+    /// to 3 digits; any code listed in `BANKS` or registered is accepted.
     pub fn is_valid_bank(bank_code: &str) -> bool {
-        bank_code.len() == 3 && Self::banks().contains_key(bank_code)
+        Self::lookup_bank_name(bank_code).is_some()
     }
 
-    pub fn is_valid_account(bank_code: &str, account_number: &str) -> bool {
-        Nuban::new(bank_code, account_number).is_err()
+    /// The `operational` flag of the bank's `BankInfo`: `Some(false)` for
+    /// one listed in `DEFUNCT_BANKS`, `Some(true)` for any other known bank
+    /// (including those added with `register_bank`), and `None` for a code
+    /// that was never known. A code reassigned to a new bank, like Skye
+    /// Bank's `076` (now Polaris Bank), is operational.
+    pub fn is_bank_operational(bank_code: &str) -> Option<bool> {
+        #[cfg(feature = "std")]
+        if Self::custom_banks().contains_key(bank_code) {
+            return Some(true);
+        }
+        Self::lookup_bank(bank_code).map(|info| info.operational)
     }
 
-    pub fn bank_code(&self) -> &str {
-        self.0
+    /// Everything known about the built-in or defunct bank at `bank_code`,
+    /// in one lookup. Banks added with `register_bank` aren't included.
+    pub fn lookup_bank(bank_code: &str) -> Option<&'static BankInfo> {
+        BANK_INFO.iter().find(|info| info.code == bank_code)
     }
 
-    pub fn bank_name(&self) -> &str {
-        Self::banks().get(self.0).unwrap()
+    /// The code of the bank that took over the defunct bank at `bank_code`,
+    /// e.g. `"044"` (Access Bank) for Intercontinental Bank's `"069"`. Codes
+    /// that are still in use have no successor, even where they now belong
+    /// to the acquirer, like Diamond Bank's `"063"` (Access Bank) and Skye
+    /// Bank's `"076"` (Polaris Bank).
+    pub fn bank_successor(bank_code: &str) -> Option<&'static str> {
+        BANK_SUCCESSORS
+            .iter()
+            .find(|(code, _)| *code == bank_code)
+            .map(|(_, successor)| *successor)
     }
 
-    pub fn account_number(&self) -> &str {
-        self.1
+    /// Returns `true` if `bank_code` has the canonical form, exactly 3
+    /// ASCII digits, whether or not it is a known bank. See `is_valid_bank`
+    /// for membership.
+    pub fn is_well_formed_bank_code(bank_code: &str) -> bool {
+        bank_code.len() == 3 && is_digits(bank_code)
     }
 
-    pub fn check_digit(&self) -> &str {
-        self.2
+    /// `is_valid_bank` for a bank code stored as a number, e.g. `11` for
+    /// `"011"`. The code is zero-padded to 3 digits before the lookup.
+    pub fn is_valid_bank_numeric(bank_code: u16) -> bool {
+        Self::is_valid_bank(&format!("{:03}", bank_code))
     }
 
-    fn calculate_check_digit(bank_code: &'a str, account_number: &'a str) -> u32 {
-        // The Approved NUBAN format: [ABC][DEFGHIJKL][M], where
-        //   -       ABC : 3-digit Bank Code
-        //   - DEFGHIJKL : NUBAN Account Serial Number
-        //   -         M : NUBAN Check Digit
-        // https://www.cbn.gov.ng/OUT/2011/CIRCULARS/BSPD/NUBAN%20PROPOSALS%20V%200%204-%2003%2009%202010.PDF
+    /// Returns `true` if `account_number` is a valid NUBAN for `bank_code`.
+    /// Unlike `new`, this doesn't build an `Error` for invalid input.
+    ///
+    /// The bank code is checked first, so an unknown bank short-circuits
+    /// before the account number is ever inspected.
+    pub fn is_valid(bank_code: &str, account_number: &str) -> bool {
+        if !Self::is_valid_bank(bank_code)
+            || account_number.len() != 10
+            || !is_digits(account_number)
+        {
+            return false;
+        }
+        let (serial, check_digit) = account_number.split_at(9);
+        match CbnNuban.check_digit(bank_code, serial) {
+            Some(expected) => check_digit.as_bytes()[0] - b'0' == expected as u8,
+            None => true,
+        }
+    }
 
-        let nuban_chars = bank_code.chars().chain(account_number.chars());
-        let nuban_digits = nuban_chars.map(|num| num.to_digit(10).unwrap());
-        let seed = [3, 7, 3, 3, 7, 3, 3, 7, 3, 3, 7, 3].iter();
-        let check_sum: u32 = seed.zip(nuban_digits).map(|(l, r)| l * r).sum();
-        match 10 - (check_sum % 10) {
-            10 => 0,
-            x => x,
+    /// Like `is_valid`, but saying why an account is invalid.
+    pub fn classify(bank_code: &str, account_number: &str) -> Validity {
+        #[rustfmt::skip] {
+            if !Self::is_valid_bank(bank_code)  { return Validity::UnknownBank }
+            if account_number.len() != 10       { return Validity::WrongLength }
+            if !is_digits(account_number)       { return Validity::NonNumeric }
+        };
+
+        if Self::is_valid(bank_code, account_number) {
+            Validity::Valid
+        } else {
+            Validity::ChecksumFailed
         }
     }
 
-    pub fn banks() -> &'static HashMap<&'static str, &'static str> {
-        LAZY_BANKS
-            .0
-            .call_once(|| LAZY_BANKS.1.set(Some(BANKS.iter().copied().collect())));
+    /// Returns `true` if `account_number` is a valid NUBAN for `bank_code`.
+    ///
+    /// ```compile_fail
+    /// #![deny(deprecated)]
+    /// nuban::Nuban::is_valid_account("058", "0152792740");
+    /// ```
+    #[deprecated(note = "use `Nuban::is_valid` instead")]
+    pub fn is_valid_account(bank_code: &str, account_number: &str) -> bool {
+        Nuban::new(bank_code, account_number).is_ok()
+    }
 
-        unsafe {
-            if let Some(ref banks) = *LAZY_BANKS.1.as_ptr() {
-                return banks;
+    /// Looks up the bank code for a bank name, ignoring case and any
+    /// surrounding whitespace. Registered banks are searched first.
+    pub fn code_for_bank(name: &str) -> Option<&'static str> {
+        let name = name.trim();
+        #[cfg(feature = "std")]
+        {
+            let custom_banks = Self::custom_banks();
+            let mut custom = custom_banks.iter();
+            if let Some((code, _)) =
+                custom.find(|(_, (bank_name, _))| bank_name.eq_ignore_ascii_case(name))
+            {
+                return Some(code);
             }
-            unreachable!()
         }
+        Self::all_banks()
+            .find(|(_, bank_name)| bank_name.eq_ignore_ascii_case(name))
+            .map(|(code, _)| code)
     }
 
-    pub fn bank_matches(account_number: &str) -> Result<impl Iterator<Item = (&str, &str)>, Error> {
-        #[rustfmt::skip] if account_number.len() != 10  { Err(Error::InvalidAccountNumber)? };
-        Ok(BANKS
+    /// Looks up the bank code for a name as users tend to type it, e.g.
+    /// `"GTB"`, `"gt bank"` or `"Guaranty Trust"`. Names are compared
+    /// ignoring case, extra whitespace and the words "bank" and "plc", after
+    /// trying `code_for_bank` and a few common abbreviations.
+    pub fn resolve_bank_fuzzy(input: &str) -> Option<&'static str> {
+        if let Some(code) = Self::code_for_bank(input) {
+            return Some(code);
+        }
+        let input = normalize_bank_name(input);
+        if input.is_empty() {
+            return None;
+        }
+        BANK_ALIASES
             .iter()
-            .copied()
-            .filter(move |(code, _)| Self::account_number_parts(code, account_number).is_ok()))
+            .find(|(alias, _)| *alias == input)
+            .map(|(_, code)| *code)
+            .or_else(|| {
+                Self::all_banks()
+                    .find(|(_, name)| normalize_bank_name(name) == input)
+                    .map(|(code, _)| code)
+            })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn test_returns_new_nuban_instance() {
-        let account = Nuban::new("058", "0152792740");
-        assert_eq!(account.unwrap(), Nuban("058", "0152792740", "0"));
+    /// The number of built-in banks in `BANKS`, not counting any added with
+    /// `register_bank`.
+    pub fn bank_count() -> usize {
+        BANKS.len()
     }
 
-    #[test]
-    fn test_returns_false_for_invalid_account() {
-        let account = Nuban::new("058", "0982736625");
-        assert!(account.is_err());
+    /// Same as `is_valid_bank`.
+    pub fn has_bank_code(code: &str) -> bool {
+        Self::is_valid_bank(code)
     }
 
-    #[test]
-    fn test_returns_true_for_valid_account() {
-        let account = Nuban::new("058", "0152792740");
-        assert!(account.is_ok());
+    /// Returns `true` if a built-in or registered bank has this name, using
+    /// the same matching as `code_for_bank`.
+    pub fn has_bank_name(name: &str) -> bool {
+        Self::code_for_bank(name).is_some()
     }
 
-    #[test]
-    fn test_calculate_check_digit() {
-        let correct_check_digit = Nuban::calculate_check_digit("058", "0152792740");
-        assert_eq!(correct_check_digit, 0);
+    pub fn to_owned(&self) -> OwnedNuban {
+        OwnedNuban {
+            bank_code: self.0.into(),
+            account_number: self.1.into(),
+        }
     }
 
-    #[test]
-    fn test_get_bank_name() {
-        let account = Nuban::new("058", "0152792740").unwrap();
-        assert_eq!(account.bank_name(), String::from("Guaranty Trust Bank"));
+    pub fn to_bank_code_string(&self) -> String {
+        self.0.into()
+    }
+
+    pub fn to_account_string(&self) -> String {
+        self.1.into()
+    }
+
+    pub fn bank_code(&self) -> &str {
+        self.0
+    }
+
+    /// The bank's NIP institution code, for NIBSS Instant Payment
+    /// transfers; see `NIP_CODES`.
+    pub fn nip_code(&self) -> Option<&'static str> {
+        if let Some((_, nip_code)) = NIP_CODES.iter().find(|(code, _)| *code == self.0) {
+            return Some(nip_code);
+        }
+        Self::all_banks()
+            .map(|(code, _)| code)
+            .find(|code| code.len() == 6 && *code == self.0)
+    }
+
+    /// The bank code as a number, e.g. `58` for `"058"`. `None` for codes
+    /// that don't fit in a `u16`, such as the 6-digit fintech codes.
+    pub fn bank_code_as_u16(&self) -> Option<u16> {
+        self.0.parse().ok()
+    }
+
+    /// The bank's name, or the bank code itself (e.g. `"999"`) if the bank
+    /// isn't known, such as when the `Nuban` was built against a
+    /// `BankRegistry` or the bank was removed with `Nuban::unregister_bank`.
+    /// Use `try_bank_name` to tell these cases apart.
+    pub fn bank_name(&self) -> &str {
+        self.bank_name_or(self.0)
+    }
+
+    pub fn try_bank_name(&self) -> Option<&str> {
+        Self::lookup_bank_name(self.0)
+    }
+
+    /// Like `try_bank_name`, but falling back to `default`.
+    pub fn bank_name_or<'b>(&self, default: &'b str) -> &'b str {
+        Self::lookup_bank_name(self.0).unwrap_or(default)
+    }
+
+    /// Registers a bank at runtime, e.g. one added by the CBN after this
+    /// crate was released.
+    ///
+    /// Registered banks are consulted before the built-in `BANKS`, so
+    /// registering an existing code overrides its built-in name. As with
+    /// `BankRegistry::with_bank`, only codes made of ASCII digits validate.
+    #[cfg(feature = "std")]
+    pub fn register_bank(code: &'static str, name: &'static str) {
+        Self::register_bank_with_category(code, name, BankCategory::Unknown);
+    }
+
+    /// Like `register_bank`, but also recording what kind of bank it is.
+    #[cfg(feature = "std")]
+    pub fn register_bank_with_category(
+        code: &'static str,
+        name: &'static str,
+        category: BankCategory,
+    ) {
+        Self::custom_banks_mut().insert(code, (name, category));
+    }
+
+    /// Removes a bank added with `register_bank`. Built-in banks cannot be
+    /// removed, but an override of one is dropped.
+    #[cfg(feature = "std")]
+    pub fn unregister_bank(code: &str) {
+        Self::custom_banks_mut().remove(code);
+    }
+
+    // A panic can't leave the map half-updated (each write is a single
+    // insert or remove), so a poisoned lock is still safe to use.
+    #[cfg(feature = "std")]
+    fn custom_banks() -> RwLockReadGuard<'static, HashMap<&'static str, CustomBank>> {
+        let custom_banks = CUSTOM_BANKS.get_or_init(Default::default);
+        custom_banks.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[cfg(feature = "std")]
+    fn custom_banks_mut() -> RwLockWriteGuard<'static, HashMap<&'static str, CustomBank>> {
+        let custom_banks = CUSTOM_BANKS.get_or_init(Default::default);
+        custom_banks.write().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[cfg(feature = "std")]
+    fn lookup_bank_name(bank_code: &str) -> Option<&'static str> {
+        let custom = Self::custom_banks().get(bank_code).map(|(name, _)| *name);
+        custom.or_else(|| Self::banks().get(bank_code).copied())
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn lookup_bank_name(bank_code: &str) -> Option<&'static str> {
+        Self::all_banks()
+            .find(|(code, _)| *code == bank_code)
+            .map(|(_, name)| name)
+    }
+
+    /// The kind of bank this account is held at. Banks registered without
+    /// a category are `BankCategory::Unknown`.
+    pub fn bank_category(&self) -> BankCategory {
+        Self::lookup_bank_category(self.0)
+    }
+
+    /// For unknown banks the name is the bank code, as with `bank_name`.
+    pub fn bank(&self) -> Bank<'_> {
+        Bank {
+            code: self.0,
+            name: self.bank_name(),
+            category: self.bank_category(),
+        }
+    }
+
+    /// The account as a JSON object with `bank_code`, `account_number`,
+    /// `check_digit` and `bank_name` fields, for API documentation and
+    /// responses. `bank_name` is `null` if the bank is no longer known.
+    #[cfg(feature = "serde_json")]
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "bank_code": self.0,
+            "account_number": self.1,
+            "check_digit": self.2,
+            "bank_name": self.try_bank_name(),
+        })
+    }
+
+    fn lookup_bank_category(bank_code: &str) -> BankCategory {
+        #[cfg(feature = "std")]
+        if let Some((_, category)) = Self::custom_banks().get(bank_code) {
+            return *category;
+        }
+        BANKS
+            .iter()
+            .find(|(code, ..)| *code == bank_code)
+            .map_or(BankCategory::Unknown, |(.., category)| *category)
+    }
+
+    pub fn account_number(&self) -> &str {
+        self.1
+    }
+
+    /// The 9-digit account serial, i.e. the account number without its
+    /// check digit.
+    pub fn serial(&self) -> &str {
+        &self.1[..9]
+    }
+
+    pub fn check_digit(&self) -> &str {
+        self.2
+    }
+
+    /// The bank code, account number and check digit in one call, e.g. for
+    /// destructuring.
+    pub fn as_parts(&self) -> (&'a str, &'a str, &'a str) {
+        (self.0, self.1, self.2)
+    }
+
+    /// The check digit as a `char`, e.g. `'0'`.
+    pub fn check_digit_char(&self) -> char {
+        char::from(self.2.as_bytes()[0])
+    }
+
+    /// The check digit as a number, read from the stored digit rather than
+    /// recomputed.
+    pub fn check_digit_value(&self) -> u32 {
+        u32::from(self.2.as_bytes()[0] - b'0')
+    }
+
+    /// The numeric value of each digit of the bank code and then the account
+    /// number, i.e. 13 digits for a 3-digit bank code.
+    pub fn digits(&self) -> impl Iterator<Item = u8> + 'a {
+        self.0.bytes().chain(self.1.bytes()).map(|b| b - b'0')
+    }
+
+    /// Returns `true` if the check digit is the one `CbnNuban` gives. This
+    /// always checks the CBN weights, so it can be `false` for an account
+    /// from `new_lenient`, or from `new_with_registry` at a bank with its own
+    /// algorithm.
+    pub fn check_digit_matches(&self) -> bool {
+        Self::verify_check_digit(&CbnNuban, self.0, self.1).is_ok()
+    }
+
+    /// Compares two accounts in constant time, so the comparison doesn't
+    /// leak where the first differing digit is. Use this instead of `==`
+    /// when checking user input against a stored account.
+    pub fn ct_eq(&self, other: &Nuban) -> bool {
+        ct_eq_bytes(self.0.as_bytes(), other.0.as_bytes())
+            & ct_eq_bytes(self.serial().as_bytes(), other.serial().as_bytes())
+            & ct_eq_bytes(self.2.as_bytes(), other.2.as_bytes())
+    }
+
+    /// Returns `true` if `other` is this account number with exactly one
+    /// pair of adjacent, different digits swapped, a common data entry typo.
+    pub fn is_transposition_of(&self, other: &str) -> bool {
+        let (ours, theirs) = (self.1.as_bytes(), other.as_bytes());
+        if ours.len() != theirs.len() {
+            return false;
+        }
+        let mut differences = (0..ours.len()).filter(|&i| ours[i] != theirs[i]);
+        match (differences.next(), differences.next(), differences.next()) {
+            (Some(i), Some(j), None) => j == i + 1 && ours[i] == theirs[j] && ours[j] == theirs[i],
+            _ => false,
+        }
+    }
+
+    /// Re-verifies a submitted account against this known-good one, e.g. in
+    /// KYC flows. Account numbers are compared in constant time, as with
+    /// `ct_eq`. An invalid submission is reported before any mismatch.
+    pub fn verify_against(&self, submitted_bank: &str, submitted_account: &str) -> VerifyResult {
+        if !Self::is_valid(submitted_bank, submitted_account) {
+            VerifyResult::SubmittedInvalid
+        } else if self.0 != submitted_bank {
+            VerifyResult::BankMismatch
+        } else if !ct_eq_bytes(self.1.as_bytes(), submitted_account.as_bytes()) {
+            VerifyResult::AccountMismatch
+        } else {
+            VerifyResult::Match
+        }
+    }
+
+    /// Returns `true` if `other` is the same account, however each was built,
+    /// i.e. both have the same 13-digit form.
+    pub fn same_account(&self, other: &OwnedNuban) -> bool {
+        *self == other.as_ref()
+    }
+
+    /// The same 13-digit string as `Display`, built without the formatting
+    /// machinery.
+    pub fn to_compact_string(&self) -> String {
+        let mut compact = String::with_capacity(self.0.len() + self.1.len());
+        compact.push_str(self.0);
+        compact.push_str(self.1);
+        compact
+    }
+
+    /// A single-line payload for encoding in a QR code or barcode, e.g.
+    /// `"NUBAN|058|0152792740|Guaranty Trust Bank"`. Read it back with
+    /// `from_qr_payload`. The name is `bank_name`, so an unknown bank is
+    /// named by its code.
+    pub fn to_qr_payload(&self) -> String {
+        format!("NUBAN|{}|{}|{}", self.0, self.1, self.bank_name())
+    }
+
+    /// A multi-line, human-readable description of the account, e.g. for
+    /// CLI output:
+    ///
+    /// ```text
+    /// Bank: Guaranty Trust Bank (058)
+    /// Account: 0152792740
+    /// Check digit: 0
+    /// ```
+    ///
+    /// As with `bank_name`, an unknown bank is named by its code.
+    pub fn summary(&self) -> String {
+        format!(
+            "Bank: {} ({})\nAccount: {}\nCheck digit: {}",
+            self.bank_name(),
+            self.0,
+            self.1,
+            self.2
+        )
+    }
+
+    /// Formats the account number in groups, e.g. `[2, 4, 4]` with `' '`
+    /// gives `"01 5279 2740"`. If the group sizes don't add up to the 10
+    /// account digits (or any is zero), the account number is returned
+    /// ungrouped.
+    pub fn format_grouped(&self, group_sizes: &[usize], separator: char) -> String {
+        let total = group_sizes
+            .iter()
+            .try_fold(0usize, |total, &size| total.checked_add(size));
+        if total != Some(self.1.len()) || group_sizes.contains(&0) {
+            return self.1.into();
+        }
+
+        let mut grouped = String::with_capacity(self.1.len() + group_sizes.len());
+        let mut rest = self.1;
+        for (i, &size) in group_sizes.iter().enumerate() {
+            if i > 0 {
+                grouped.push(separator);
+            }
+            let (group, tail) = rest.split_at(size);
+            grouped.push_str(group);
+            rest = tail;
+        }
+        grouped
+    }
+
+    /// The full NUBAN with all but the last 3 account digits masked, e.g.
+    /// `"058*******740"`.
+    pub fn mask(&self) -> String {
+        self.mask_with(3, '*')
+    }
+
+    /// Like `mask`, but showing the last `visible_suffix` account digits
+    /// (clamped to the account length) and masking the rest with
+    /// `mask_char`.
+    pub fn mask_with(&self, visible_suffix: usize, mask_char: char) -> String {
+        let hidden = self.1.len() - visible_suffix.min(self.1.len());
+        let mask = core::iter::repeat(mask_char).take(hidden);
+        self.0
+            .chars()
+            .chain(mask)
+            .chain(self.1[hidden..].chars())
+            .collect()
+    }
+
+    /// Returns `true` if this looks like a wallet account numbered after its
+    /// holder's phone number: held at a payment service bank or mobile money
+    /// operator, and shaped like a Nigerian mobile number without its
+    /// leading `0`, e.g. `8031234567`.
+    pub fn is_phone_derived(&self) -> bool {
+        let wallet = matches!(
+            self.bank_category(),
+            BankCategory::PaymentService | BankCategory::MobileMoney
+        );
+        let digits = self.1.as_bytes();
+        wallet && matches!(digits[0], b'7'..=b'9') && matches!(digits[1], b'0' | b'1')
+    }
+
+    /// The phone number a phone-derived account was numbered after, e.g.
+    /// `"08031234567"`; see `is_phone_derived`.
+    pub fn as_phone_number(&self) -> Option<String> {
+        if !self.is_phone_derived() {
+            return None;
+        }
+        Some(format!("0{}", self.1))
+    }
+
+    /// The bank name and an account number showing only its first 3 and
+    /// last 2 digits, e.g. `"Guaranty Trust Bank: 015*****40"`, for audit
+    /// logs. As with `bank_name`, unknown banks are shown by code.
+    pub fn log_safe(&self) -> String {
+        let mask = core::iter::repeat('*').take(self.1.len() - 5);
+        let account_number: String = self.1[..3]
+            .chars()
+            .chain(mask)
+            .chain(self.1[self.1.len() - 2..].chars())
+            .collect();
+        format!("{}: {}", self.bank_name(), account_number)
+    }
+
+    /// Computes the NUBAN check digit for a 9-digit account serial at the
+    /// given bank.
+    ///
+    /// Only 3-digit bank codes have a NUBAN check digit, so this returns
+    /// `Error::InvalidBankCode` for longer (e.g. fintech) codes.
+    pub fn check_digit_for(bank_code: &str, serial: &str) -> Result<u32, Error> {
+        let remainder = Self::checksum_remainder(bank_code, serial)?;
+        Ok((10 - remainder) % 10)
+    }
+
+    /// The weighted digit sum of bank code and serial, modulo 10, i.e. the
+    /// step before the check digit is taken as `10 - remainder` (or `0` for
+    /// a remainder of `0`). Accepts the same input as `check_digit_for`.
+    pub fn checksum_remainder(bank_code: &str, serial: &str) -> Result<u32, Error> {
+        #[rustfmt::skip] {
+            if !Self::is_valid_bank(bank_code) || !Self::is_well_formed_bank_code(bank_code) { Err(Error::bank_code(bank_code))? }
+            if serial.len() != 9 { Err(Error::account_number(serial, AccountError::WrongLength))? }
+            if !is_digits(serial) { Err(Error::account_number(serial, AccountError::NonDigit))? }
+        };
+
+        Ok(Self::weighted_remainder(
+            bank_code.as_bytes(),
+            serial.as_bytes(),
+        ))
+    }
+
+    /// Each of the 12 digits of bank code and serial as `(digit, weight,
+    /// product)`, showing how `checksum_remainder` is reached, e.g. for
+    /// teaching or debugging. Accepts the same input as `check_digit_for`.
+    pub fn weighted_digits(bank_code: &str, serial: &str) -> Result<Vec<(u32, u32, u32)>, Error> {
+        Self::checksum_remainder(bank_code, serial)?;
+        let digits = bank_code
+            .bytes()
+            .chain(serial.bytes())
+            .map(|b| u32::from(b - b'0'));
+        Ok(digits
+            .zip(SEED.iter())
+            .map(|(digit, &weight)| (digit, weight, digit * weight))
+            .collect())
+    }
+
+    /// How many valid account numbers `bank_code` has. Each of the 10^9
+    /// serials has exactly one valid check digit, so this is 1,000,000,000
+    /// for 3-digit bank codes. Banks without check digits accept any 10
+    /// digits, giving 10^10.
+    pub fn valid_account_space(bank_code: &str) -> Result<u64, Error> {
+        #[rustfmt::skip] if !Self::is_valid_bank(bank_code) { Err(Error::bank_code(bank_code))? };
+        if has_check_digit(bank_code) {
+            Ok(1_000_000_000)
+        } else {
+            Ok(10_000_000_000)
+        }
+    }
+
+    /// Builds the full 10-digit account number for a 9-digit serial by
+    /// appending its check digit. The result always passes `Nuban::new`.
+    pub fn account_number_for(bank_code: &str, serial: &str) -> Result<String, Error> {
+        let check_digit = Self::check_digit_for(bank_code, serial)?;
+        Ok(format!("{}{}", serial, check_digit))
+    }
+
+    /// Like `account_number_for`, but returning the validated account, e.g.
+    /// `from_serial("058", "015279274")` for `058`/`0152792740`. It is an
+    /// `OwnedNuban` since the full account number is built here.
+    pub fn from_serial(bank_code: &str, serial: &str) -> Result<OwnedNuban, Error> {
+        let account_number = Self::account_number_for(bank_code, serial)?;
+        OwnedNuban::new(bank_code.into(), account_number)
+    }
+
+    /// Suggests the intended account when the only problem is a wrong check
+    /// digit, by recomputing it from the serial. `None` if the account is
+    /// already valid or has any other problem.
+    pub fn suggest_correction(bank_code: &str, account_number: &str) -> Option<OwnedNuban> {
+        let account = Nuban::new_lenient(bank_code, account_number).ok()?;
+        if account.check_digit_matches() {
+            return None;
+        }
+        let account_number = Self::account_number_for(bank_code, account.serial()).ok()?;
+        OwnedNuban::new(bank_code.into(), account_number).ok()
+    }
+
+    /// Builds `count` valid accounts at `bank_code` with consecutive serials
+    /// from `start_serial`, e.g. for test fixtures. Stops early rather than
+    /// going past the last 9-digit serial, `999999999`.
+    pub fn sequential_valid(
+        bank_code: &str,
+        start_serial: u32,
+        count: usize,
+    ) -> Result<Vec<OwnedNuban>, Error> {
+        (start_serial..=999_999_999)
+            .take(count)
+            .map(|serial| {
+                let account_number =
+                    Self::account_number_for(bank_code, &format!("{:09}", serial))?;
+                OwnedNuban::new(bank_code.into(), account_number)
+            })
+            .collect()
+    }
+
+    /// Builds the valid account for `serial` at every built-in bank that
+    /// uses NUBAN check digits, e.g. to see which banks a serial could
+    /// belong to. An invalid serial yields no accounts.
+    pub fn valid_accounts_for_serial(serial: &str) -> Vec<(&'static str, OwnedNuban)> {
+        Self::all_banks()
+            .filter_map(|(code, _)| {
+                let account_number = Self::account_number_for(code, serial).ok()?;
+                let account = OwnedNuban::new(code.into(), account_number).ok()?;
+                Some((code, account))
+            })
+            .collect()
+    }
+
+    /// Generates a random, valid account at `bank_code`, e.g. for fixtures
+    /// and load tests. Like `check_digit_for`, this only supports banks with
+    /// 3-digit codes.
+    #[cfg(all(feature = "rand", feature = "std"))]
+    pub fn random_valid(bank_code: &str) -> Result<OwnedNuban, Error> {
+        Self::random_valid_with(bank_code, &mut rand::thread_rng())
+    }
+
+    /// Like `random_valid`, but drawing the serial from `rng` so a seeded
+    /// generator gives reproducible accounts.
+    #[cfg(feature = "rand")]
+    pub fn random_valid_with(
+        bank_code: &str,
+        rng: &mut impl rand::Rng,
+    ) -> Result<OwnedNuban, Error> {
+        let serial = format!("{:09}", rng.gen_range(0..1_000_000_000u32));
+        let account_number = Self::account_number_for(bank_code, &serial)?;
+        OwnedNuban::new(bank_code.into(), account_number)
+    }
+
+    /// Panics, i.e. fails to compile when evaluated in a `const`, unless the
+    /// account is valid at a built-in bank. Used by `validate!`.
+    #[doc(hidden)]
+    pub const fn __assert_valid(bank_code: &str, account_number: &str) {
+        let account = account_number.as_bytes();
+        assert!(is_builtin_bank(bank_code.as_bytes()), "invalid bank code");
+        assert!(account.len() == 10, "invalid account number: wrong length");
+        let mut i = 0;
+        while i < account.len() {
+            assert!(
+                account[i].is_ascii_digit(),
+                "invalid account number: contains a non-digit character"
+            );
+            i += 1;
+        }
+        if has_check_digit(bank_code) {
+            let expected = Self::calculate_check_digit(bank_code.as_bytes(), account);
+            assert!(
+                expected == (account[9] - b'0') as u32,
+                "invalid account number: check digit mismatch"
+            );
+        }
+    }
+
+    /// Builds a `Nuban` already checked by `__assert_valid`. Used by
+    /// `validate!`.
+    #[doc(hidden)]
+    pub fn __from_validated(bank_code: &'a str, account_number: &'a str) -> Self {
+        Self::well_formed(bank_code, account_number).expect("validated at compile time")
+    }
+
+    /// The version of the CBN NUBAN proposal this crate implements.
+    pub const fn spec_version() -> &'static str {
+        "CBN NUBAN v0.4 (2010-09-03)"
+    }
+
+    /// Where the CBN publishes the NUBAN proposal named by `spec_version`.
+    pub const fn spec_url() -> &'static str {
+        "https://www.cbn.gov.ng/OUT/2011/CIRCULARS/BSPD/NUBAN%20PROPOSALS%20V%200%204-%2003%2009%202010.PDF"
+    }
+
+    /// The weights applied to the 12 digits of bank code and serial, as
+    /// published by the CBN.
+    pub const fn check_digit_weights() -> &'static [u32; 12] {
+        &SEED
+    }
+
+    /// Computes the NUBAN check digit from the raw ASCII digits of a 3-digit
+    /// bank code and 9-digit serial. Unlike `check_digit_for` this doesn't
+    /// validate the bank, but it is a `const fn`, so fixed accounts can be
+    /// checked at compile time:
+    ///
+    /// ```
+    /// use nuban::Nuban;
+    ///
+    /// const CHECK_DIGIT: u32 = Nuban::calculate_check_digit(b"058", b"015279274");
+    /// assert_eq!(CHECK_DIGIT, 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in a `const` context) if there are fewer
+    /// than 12 digits in total, or any of the first 12 isn't an ASCII digit.
+    pub const fn calculate_check_digit(bank_code: &[u8], serial: &[u8]) -> u32 {
+        match 10 - Self::weighted_remainder(bank_code, serial) {
+            10 => 0,
+            x => x,
+        }
+    }
+
+    /// Like `calculate_check_digit`, but for any number of ASCII digits,
+    /// weighting them with the NUBAN pattern `3, 7, 3` repeated to length.
+    /// For 12 digits this is the canonical NUBAN check digit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `digits` isn't an ASCII digit.
+    pub const fn calculate_check_digit_generic(digits: &[u8]) -> u32 {
+        const PATTERN: [u32; 3] = [3, 7, 3];
+        let mut check_sum = 0;
+        let mut i = 0;
+        while i < digits.len() {
+            assert!(digits[i].is_ascii_digit(), "not an ASCII digit");
+            check_sum += PATTERN[i % PATTERN.len()] * (digits[i] - b'0') as u32;
+            i += 1;
+        }
+        (10 - check_sum % 10) % 10
+    }
+
+    const fn weighted_remainder(bank_code: &[u8], serial: &[u8]) -> u32 {
+        // The Approved NUBAN format: [ABC][DEFGHIJKL][M], where
+        //   -       ABC : 3-digit Bank Code
+        //   - DEFGHIJKL : NUBAN Account Serial Number
+        //   -         M : NUBAN Check Digit
+        // https://www.cbn.gov.ng/OUT/2011/CIRCULARS/BSPD/NUBAN%20PROPOSALS%20V%200%204-%2003%2009%202010.PDF
+
+        assert!(
+            bank_code.len() + serial.len() >= SEED.len(),
+            "too few digits"
+        );
+        let mut check_sum = 0;
+        let mut i = 0;
+        while i < SEED.len() {
+            let digit = if i < bank_code.len() {
+                bank_code[i]
+            } else {
+                serial[i - bank_code.len()]
+            };
+            assert!(digit.is_ascii_digit(), "not an ASCII digit");
+            check_sum += SEED[i] * (digit - b'0') as u32;
+            i += 1;
+        }
+        check_sum % 10
+    }
+
+    #[cfg(feature = "std")]
+    pub fn banks() -> &'static HashMap<&'static str, &'static str> {
+        BANKS_MAP.get_or_init(|| Self::all_banks().collect())
+    }
+
+    /// Iterates over the supported `(code, name)` pairs in the order they
+    /// appear in `BANKS`, unlike `banks()` which has no ordering guarantee.
+    pub fn all_banks() -> impl Iterator<Item = (&'static str, &'static str)> {
+        BANKS.iter().map(|&(code, name, _)| (code, name))
+    }
+
+    /// The built-in banks as a JSON array of `{"code", "name"}` objects, in
+    /// `BANKS` order, e.g. to serve the bank list to a frontend.
+    #[cfg(feature = "serde_json")]
+    pub fn banks_as_json() -> String {
+        let banks = Self::all_banks()
+            .map(|(code, name)| serde_json::json!({ "code": code, "name": name }))
+            .collect();
+        format!("{}", serde_json::Value::Array(banks))
+    }
+
+    /// The built-in `(code, name)` pairs sorted by bank code, for a stable
+    /// order in displays and snapshot tests.
+    pub fn banks_sorted() -> Vec<(&'static str, &'static str)> {
+        let mut banks: Vec<_> = Self::all_banks().collect();
+        banks.sort_unstable();
+        banks
+    }
+
+    /// The built-in `(code, name)` pairs whose code starts with `prefix`, in
+    /// `BANKS` order, e.g. for grouping banks in a UI. An empty prefix
+    /// matches every bank.
+    pub fn banks_with_prefix(prefix: &str) -> Vec<(&'static str, &'static str)> {
+        Self::all_banks()
+            .filter(|(code, _)| code.starts_with(prefix))
+            .collect()
+    }
+
+    pub fn bank_matches(
+        account_number: &'a str,
+    ) -> Result<impl Iterator<Item = (&'static str, &'static str)> + 'a, Error> {
+        #[rustfmt::skip] {
+            if account_number.len() != 10 { Err(Error::account_number(account_number, AccountError::WrongLength))? }
+            if !is_digits(account_number) { Err(Error::account_number(account_number, AccountError::NonDigit))? }
+        };
+        Ok(Self::all_banks().filter(move |(code, _)| {
+            Self::verify_check_digit(&CbnNuban, code, account_number).is_ok()
+        }))
+    }
+
+    /// The codes of the built-in banks `account_number` is valid at, in
+    /// `BANKS` order. With only 10 possible check digits many banks match,
+    /// including every bank without check digits, so this narrows down the
+    /// bank but doesn't identify it. Invalid input gives no candidates.
+    pub fn candidate_banks(account_number: &str) -> Vec<&'static str> {
+        match Nuban::bank_matches(account_number) {
+            Ok(matches) => matches.map(|(code, _)| code).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Lazily validates each `(bank_code, account_number)` pair of an iterator,
+/// e.g. to stream large inputs or stop at the first valid account.
+#[derive(Clone, Debug)]
+pub struct ValidatedAccounts<I> {
+    accounts: I,
+}
+
+impl<'a, I> ValidatedAccounts<I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    pub fn new(accounts: impl IntoIterator<IntoIter = I>) -> Self {
+        ValidatedAccounts {
+            accounts: accounts.into_iter(),
+        }
+    }
+}
+
+impl<'a, I> Iterator for ValidatedAccounts<I>
+where
+    I: Iterator<Item = (&'a str, &'a str)>,
+{
+    type Item = Result<Nuban<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (bank_code, account_number) = self.accounts.next()?;
+        Some(Nuban::new(bank_code, account_number))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.accounts.size_hint()
+    }
+}
+
+/// An owned counterpart to `Nuban`, for when the validated account has to
+/// outlive the strings it was built from.
+#[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
+pub struct OwnedNuban {
+    bank_code: String,
+    account_number: String,
+}
+
+impl OwnedNuban {
+    pub fn new(bank_code: String, account_number: String) -> Result<Self, Error> {
+        Nuban::new(&bank_code, &account_number)?;
+        Ok(OwnedNuban {
+            bank_code,
+            account_number,
+        })
+    }
+
+    pub fn as_ref(&self) -> Nuban<'_> {
+        Nuban(
+            &self.bank_code,
+            &self.account_number,
+            &self.account_number[9..],
+        )
+    }
+
+    pub fn bank_code(&self) -> &str {
+        &self.bank_code
+    }
+
+    /// The bank's name, or the bank code itself if the bank isn't known; see
+    /// `Nuban::bank_name`.
+    pub fn bank_name(&self) -> &str {
+        self.try_bank_name().unwrap_or(&self.bank_code)
+    }
+
+    pub fn try_bank_name(&self) -> Option<&str> {
+        Nuban::lookup_bank_name(&self.bank_code)
+    }
+
+    pub fn account_number(&self) -> &str {
+        &self.account_number
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.account_number[..9]
+    }
+
+    pub fn check_digit(&self) -> &str {
+        &self.account_number[9..]
+    }
+}
+
+impl fmt::Display for OwnedNuban {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_ref(), f)
+    }
+}
+
+impl PartialEq<OwnedNuban> for Nuban<'_> {
+    fn eq(&self, other: &OwnedNuban) -> bool {
+        *self == other.as_ref()
+    }
+}
+
+impl PartialEq<Nuban<'_>> for OwnedNuban {
+    fn eq(&self, other: &Nuban<'_>) -> bool {
+        self.as_ref() == *other
+    }
+}
+
+/// Builds a `Nuban` from literals that are validated at compile time, so a
+/// typo in a hardcoded account fails the build:
+///
+/// ```
+/// let account = nuban::validate!("058", "0152792740");
+/// assert_eq!(account.bank_name(), "Guaranty Trust Bank");
+/// ```
+///
+/// ```compile_fail
+/// let account = nuban::validate!("058", "0152792741");
+/// ```
+///
+/// Only the built-in `BANKS` are known at compile time, not banks added with
+/// `Nuban::register_bank`.
+#[macro_export]
+macro_rules! validate {
+    ($bank_code:expr, $account_number:expr $(,)?) => {{
+        const _: () = $crate::Nuban::__assert_valid($bank_code, $account_number);
+        $crate::Nuban::__from_validated($bank_code, $account_number)
+    }};
+}
+
+/// The NUBAN weights for the 12 digits of bank code and serial, in order.
+const SEED: [u32; 12] = [3, 7, 3, 3, 7, 3, 3, 7, 3, 3, 7, 3];
+
+/// The NUBAN check digit is only defined for canonical 3-digit bank codes;
+/// accounts at banks with longer codes are validated on format alone.
+const fn has_check_digit(bank_code: &str) -> bool {
+    bank_code.len() == 3
+}
+
+/// `==` for `const` contexts, where it isn't allowed on slices.
+const fn const_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() && a[i] == b[i] {
+        i += 1;
+    }
+    i == a.len()
+}
+
+const fn is_builtin_bank(bank_code: &[u8]) -> bool {
+    let mut i = 0;
+    while i < BANKS.len() {
+        if const_eq(BANKS[i].0.as_bytes(), bank_code) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Gathers everything known about each bank in `BANKS` and
+/// `DEFUNCT_BANKS`, for `Nuban::lookup_bank`.
+const fn bank_info() -> [BankInfo; BANKS.len() + DEFUNCT_BANKS.len()] {
+    const fn find(table: &[(&str, &'static str)], code: &str) -> Option<&'static str> {
+        let mut i = 0;
+        while i < table.len() {
+            if const_eq(table[i].0.as_bytes(), code.as_bytes()) {
+                return Some(table[i].1);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    let unknown = BankInfo {
+        code: "",
+        name: "",
+        category: BankCategory::Unknown,
+        operational: false,
+        successor: None,
+        nip_code: None,
+    };
+    let mut info = [unknown; BANKS.len() + DEFUNCT_BANKS.len()];
+    let mut i = 0;
+    while i < BANKS.len() {
+        let (code, name, category) = BANKS[i];
+        let nip_code = match find(&NIP_CODES, code) {
+            Some(nip_code) => Some(nip_code),
+            None if code.len() == 6 => Some(code),
+            None => None,
+        };
+        info[i] = BankInfo {
+            code,
+            name,
+            category,
+            operational: true,
+            successor: find(&BANK_SUCCESSORS, code),
+            nip_code,
+        };
+        i += 1;
+    }
+    let mut j = 0;
+    while j < DEFUNCT_BANKS.len() {
+        let (code, name) = DEFUNCT_BANKS[j];
+        info[i + j] = BankInfo {
+            code,
+            name,
+            // Every defunct bank listed was a commercial bank.
+            category: BankCategory::Commercial,
+            operational: false,
+            successor: find(&BANK_SUCCESSORS, code),
+            nip_code: None,
+        };
+        j += 1;
+    }
+    info
+}
+
+static BANK_INFO: [BankInfo; BANKS.len() + DEFUNCT_BANKS.len()] = bank_info();
+
+/// Only the lengths (which are public for NUBANs anyway) can short-circuit.
+fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+fn normalize_bank_name(name: &str) -> String {
+    let words: Vec<String> = name
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .filter(|word| word != "bank" && word != "plc")
+        .collect();
+    words.join(" ")
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Bank codes of any length are allowed, but only as ASCII digits.
+fn is_numeric_code(bank_code: &str) -> bool {
+    !bank_code.is_empty() && is_digits(bank_code)
+}
+
+/// The format checks shared by `Nuban::new` and `Nuban::from_bytes`, in the
+/// order `new` reports them.
+fn check_account_bytes(account_number: &[u8]) -> Result<(), AccountError> {
+    #[rustfmt::skip] {
+        if account_number.len() != 10                     { Err(AccountError::WrongLength)? }
+        if !account_number.iter().all(u8::is_ascii_digit) { Err(AccountError::NonDigit)? }
+    };
+    Ok(())
+}
+
+// The validation path must keep compiling without `std`; CI checks this
+// with `cargo build --no-default-features` alongside the regular test run.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::prelude::v1::*;
+
+    proptest::proptest! {
+        #[test]
+        fn prop_new_never_panics(bank_code in ".{0,7}", account_number in ".{0,12}") {
+            let _ = Nuban::new(&bank_code, &account_number);
+            let _ = Nuban::new_lenient(&bank_code, &account_number);
+            let _ = Nuban::new_strict(&bank_code, &account_number);
+            let _ = Nuban::new_normalized(&bank_code, &account_number);
+            let _ = Nuban::validate_collecting(&bank_code, &account_number);
+            let _ = Nuban::is_valid(&bank_code, &account_number);
+            let _ = Nuban::check_digit_for(&bank_code, &account_number);
+            let _ = CbnNuban.check_digit(&bank_code, &account_number);
+        }
+
+        #[test]
+        fn prop_digits_never_panic(bank_code in "[0-9]{0,7}", account_number in "[0-9]{0,12}") {
+            let valid = Nuban::new(&bank_code, &account_number).is_ok();
+            proptest::prop_assert_eq!(valid, Nuban::is_valid(&bank_code, &account_number));
+            let _ = Nuban::parse(&format!("{}{}", bank_code, account_number));
+            let _ = Nuban::candidate_banks(&account_number);
+        }
+
+        #[test]
+        fn prop_parse_never_panics(nuban in ".{0,16}") {
+            let _ = Nuban::parse(&nuban);
+            let _ = Nuban::parse_many(&nuban);
+            let _ = Nuban::from_bytes(nuban.as_bytes(), nuban.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_returns_new_nuban_instance() {
+        let account = Nuban::new("058", "0152792740");
+        assert_eq!(account.unwrap(), Nuban("058", "0152792740", "0"));
+    }
+
+    #[test]
+    fn test_serial() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.serial(), "015279274");
+        assert_eq!(account.check_digit(), "0");
+    }
+
+    #[test]
+    fn test_is_bank_operational() {
+        assert_eq!(Nuban::is_bank_operational("056"), Some(false));
+        assert_eq!(Nuban::is_bank_operational("069"), Some(false));
+        assert_eq!(Nuban::is_bank_operational("058"), Some(true));
+        // Skye Bank is defunct, but its code now belongs to Polaris Bank.
+        assert_eq!(Nuban::is_bank_operational("076"), Some(true));
+        assert_eq!(Nuban::is_bank_operational("000"), None);
+        for (code, _) in DEFUNCT_BANKS.iter() {
+            assert!(!Nuban::is_valid_bank(code));
+            assert!(!Nuban::lookup_bank(code).unwrap().operational);
+        }
+    }
+
+    #[test]
+    fn test_lookup_bank() {
+        assert_eq!(
+            Nuban::lookup_bank("058"),
+            Some(&BankInfo {
+                code: "058",
+                name: "Guaranty Trust Bank",
+                category: BankCategory::Commercial,
+                operational: true,
+                successor: None,
+                nip_code: Some("000013"),
+            })
+        );
+
+        let intercontinental = Nuban::lookup_bank("069").unwrap();
+        assert_eq!(intercontinental.successor, Some("044"));
+        let oceanic = Nuban::lookup_bank("056").unwrap();
+        assert!(!oceanic.operational);
+        assert_eq!(oceanic.successor, Some("050"));
+        assert_eq!(
+            Nuban::lookup_bank("090267").unwrap().nip_code,
+            Some("090267")
+        );
+        assert_eq!(Nuban::lookup_bank("000"), None);
+    }
+
+    #[test]
+    fn test_bank_successor() {
+        assert_eq!(Nuban::bank_successor("069"), Some("044"));
+        assert_eq!(Nuban::bank_successor("056"), Some("050"));
+        assert_eq!(Nuban::bank_successor("063"), None);
+        assert_eq!(Nuban::bank_successor("076"), None);
+        assert_eq!(Nuban::bank_successor("082"), None);
+        assert_eq!(Nuban::bank_successor("030"), None);
+        assert_eq!(Nuban::bank_successor("058"), None);
+        for (code, successor) in BANK_SUCCESSORS.iter() {
+            assert_eq!(Nuban::is_bank_operational(code), Some(false));
+            assert_eq!(Nuban::is_bank_operational(successor), Some(true));
+        }
+        let polaris = Nuban::lookup_bank("076").unwrap();
+        assert!(polaris.operational);
+        assert_eq!(polaris.successor, None);
+    }
+
+    #[test]
+    fn test_is_well_formed_bank_code() {
+        assert!(!Nuban::is_well_formed_bank_code("0a8"));
+        assert!(!Nuban::is_well_formed_bank_code("58"));
+        assert!(Nuban::is_well_formed_bank_code("900"));
+        assert!(!Nuban::is_valid_bank("900"));
+        assert!(Nuban::is_well_formed_bank_code("058"));
+        assert!(Nuban::is_valid_bank("058"));
+    }
+
+    #[test]
+    fn test_bank_code_as_u16() {
+        for (bank, account) in [("011", "0000000017"), ("058", "0152792740")] {
+            let code = Nuban::new(bank, account)
+                .unwrap()
+                .bank_code_as_u16()
+                .unwrap();
+            assert_eq!(format!("{:03}", code), bank);
+            assert!(Nuban::is_valid_bank_numeric(code));
+        }
+        assert_eq!(
+            Nuban::new("011", "0000000017").unwrap().bank_code_as_u16(),
+            Some(11)
+        );
+        assert_eq!(
+            Nuban::new("090267", "2001234567")
+                .unwrap()
+                .bank_code_as_u16(),
+            None
+        );
+        assert!(!Nuban::is_valid_bank_numeric(0));
+    }
+
+    #[test]
+    fn test_digits() {
+        let digits: Vec<u8> = Nuban::new("058", "0152792740").unwrap().digits().collect();
+        assert_eq!(digits, vec![0, 5, 8, 0, 1, 5, 2, 7, 9, 2, 7, 4, 0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_non_digit_registered_bank_is_rejected() {
+        let registry = BankRegistry::new()
+            .with_bank(" !1", "Punctuation Bank")
+            .with_bank("", "Bank of Nothing");
+        for code in [" !1", ""] {
+            assert_eq!(
+                Nuban::new_with_registry(&registry, code, "0152792740"),
+                Err(Error::bank_code(code))
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_strict() {
+        assert!(Nuban::new("058", "0000000001").is_ok());
+        assert_eq!(
+            Nuban::new_strict("058", "0000000001"),
+            Err(Error::account_number(
+                "0000000001",
+                AccountError::PlaceholderSerial
+            ))
+        );
+        let account_number = Nuban::account_number_for("058", "111111111").unwrap();
+        assert!(Nuban::new_strict("058", &account_number).is_err());
+        assert!(Nuban::new_strict("058", "0152792740").is_ok());
+        assert!(Nuban::new_strict("058", "0152792741").is_err());
+    }
+
+    #[test]
+    fn test_new_lenient() {
+        assert!(Nuban::new("058", "0152792741").is_err());
+        let account = Nuban::new_lenient("058", "0152792741").unwrap();
+        assert_eq!(account.check_digit(), "1");
+        assert!(!account.check_digit_matches());
+
+        assert!(Nuban::new_lenient("058", "0152792740")
+            .unwrap()
+            .check_digit_matches());
+        assert!(Nuban::new("058", "0152792740")
+            .unwrap()
+            .check_digit_matches());
+        assert!(Nuban::new_lenient("000", "0152792741").is_err());
+        assert!(Nuban::new_lenient("058", "015279274").is_err());
+        assert!(Nuban::new_lenient("058", "015279274a").is_err());
+    }
+
+    #[test]
+    fn test_from_numbers() {
+        let account = Nuban::from_numbers(58, 152792740).unwrap();
+        assert_eq!(account.bank_code(), "058");
+        assert_eq!(account.account_number(), "0152792740");
+        assert_eq!(
+            Nuban::from_numbers(58, 10_000_000_000),
+            Err(Error::account_number(
+                "10000000000",
+                AccountError::WrongLength
+            ))
+        );
+        assert_eq!(
+            Nuban::from_numbers(1058, 152792740),
+            Err(Error::bank_code("1058"))
+        );
+    }
+
+    #[test]
+    fn test_new_trimmed() {
+        let account = Nuban::new_trimmed(" 058 ", "0152792740\t").unwrap();
+        assert_eq!(account, Nuban::new("058", "0152792740").unwrap());
+        assert!(Nuban::new(" 058 ", "0152792740").is_err());
+        assert_eq!(
+            Nuban::new_trimmed("0 58", "0152792740"),
+            Err(Error::bank_code("0 58"))
+        );
+        assert!(Nuban::new_trimmed("058", "0152 792740").is_err());
+    }
+
+    #[test]
+    fn test_new_normalized() {
+        for input in [
+            "0152-792-740",
+            "015 279 2740",
+            "015.279.2740",
+            " 0152792740\t",
+        ] {
+            let account = Nuban::new_normalized("058", input).unwrap();
+            assert_eq!(account.account_number(), "0152792740");
+        }
+        assert_eq!(
+            Nuban::new_normalized("058", "0152-792-74O")
+                .unwrap_err()
+                .reason(),
+            Some(AccountError::NonDigit)
+        );
+        assert!(Nuban::new_normalized("058", "0152-792-741").is_err());
+    }
+
+    #[test]
+    fn test_bank() {
+        let nuban = Nuban::new("058", "0152792740").unwrap();
+        let bank = nuban.bank();
+        assert_eq!(bank.code, "058");
+        assert_eq!(bank.name, "Guaranty Trust Bank");
+        assert_eq!(bank.category, BankCategory::Commercial);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_to_json_value() {
+        let value = Nuban::new("058", "0152792740").unwrap().to_json_value();
+        assert_eq!(value["bank_code"], "058");
+        assert_eq!(value["account_number"], "0152792740");
+        assert_eq!(value["check_digit"], "0");
+        assert_eq!(value["bank_name"], "Guaranty Trust Bank");
+        assert_eq!(value.as_object().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_owned_strings() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let (bank_code, account_number) =
+            (account.to_bank_code_string(), account.to_account_string());
+        assert_eq!(bank_code, account.bank_code());
+        assert_eq!(account_number, account.account_number());
+    }
+
+    #[test]
+    fn test_nip_code() {
+        assert_eq!(
+            Nuban::new("058", "0152792740").unwrap().nip_code(),
+            Some("000013")
+        );
+        assert_eq!(
+            Nuban::new("090267", "2001234567").unwrap().nip_code(),
+            Some("090267")
+        );
+
+        let account = Nuban::from_nip_code("000013", "0152792740").unwrap();
+        assert_eq!(account.bank_code(), "058");
+        assert_eq!(account, Nuban::new("058", "0152792740").unwrap());
+        assert!(Nuban::from_nip_code("090267", "2001234567").is_ok());
+        assert_eq!(
+            Nuban::from_nip_code("058", "0152792740"),
+            Err(Error::bank_code("058"))
+        );
+
+        for (code, nip_code) in NIP_CODES.iter() {
+            assert!(Nuban::is_valid_bank(code));
+            assert_eq!(nip_code.len(), 6);
+        }
+    }
+
+    #[test]
+    fn test_as_parts() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let (bank_code, account_number, check_digit) = account.as_parts();
+        assert_eq!(bank_code, account.bank_code());
+        assert_eq!(account_number, account.account_number());
+        assert_eq!(check_digit, account.check_digit());
+        assert_eq!(account.as_parts(), ("058", "0152792740", "0"));
+    }
+
+    #[test]
+    fn test_check_digit_char() {
+        assert_eq!(
+            Nuban::new("058", "0152792740").unwrap().check_digit_char(),
+            '0'
+        );
+        assert_eq!(
+            Nuban::new("057", "2209514399").unwrap().check_digit_char(),
+            '9'
+        );
+    }
+
+    #[test]
+    fn test_check_digit_value() {
+        assert_eq!(
+            Nuban::new("058", "0152792740").unwrap().check_digit_value(),
+            0
+        );
+        for (bank, account) in [("057", "2209514399"), ("058", "0000000018")] {
+            let nuban = Nuban::new(bank, account).unwrap();
+            let expected = Nuban::calculate_check_digit(bank.as_bytes(), nuban.serial().as_bytes());
+            assert_eq!(nuban.check_digit_value(), expected);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut accounts = vec![
+            Nuban::new("058", "0152792740").unwrap(),
+            Nuban::new("057", "2209514399").unwrap(),
+            Nuban::new("058", "0000000018").unwrap(),
+            Nuban::new("035", "2209514399").unwrap(),
+        ];
+        accounts.sort();
+        assert_eq!(
+            accounts,
+            vec![
+                Nuban("035", "2209514399", "9"),
+                Nuban("057", "2209514399", "9"),
+                Nuban("058", "0000000018", "8"),
+                Nuban("058", "0152792740", "0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_btree_set_dedupes() {
+        let set = vec![
+            ("058", "0152792740"),
+            ("057", "2209514399"),
+            ("058", "0152792740"),
+        ]
+        .into_iter()
+        .map(|(code, account)| Nuban::new(code, account).unwrap())
+        .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().next().unwrap().bank_code(), "057");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_set() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(Nuban::new("058", "0152792740").unwrap());
+        set.insert(Nuban::new("058", "0152792740").unwrap());
+        assert_eq!(set.len(), 1);
+
+        set.insert(Nuban::new("057", "2209514399").unwrap());
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Nuban("058", "0152792740", "0")));
+        assert!(set.contains(&Nuban("057", "2209514399", "9")));
+    }
+
+    #[test]
+    fn test_owned_nuban() {
+        fn build(input: &str) -> OwnedNuban {
+            let (bank_code, account_number) = input.split_at(3);
+            OwnedNuban::new(bank_code.to_string(), account_number.to_string()).unwrap()
+        }
+
+        let account = build(&String::from("0580152792740"));
+        assert_eq!(account.bank_code(), "058");
+        assert_eq!(account.bank_name(), "Guaranty Trust Bank");
+        assert_eq!(account.account_number(), "0152792740");
+        assert_eq!(account.serial(), "015279274");
+        assert_eq!(account.check_digit(), "0");
+        assert_eq!(account.to_string(), "0580152792740");
+        assert_eq!(account.as_ref(), Nuban("058", "0152792740", "0"));
+        assert_eq!(account.as_ref().to_owned(), account);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_owned_nuban_bank_name_after_unregister() {
+        let registered = RegisteredBank::new("993", "Bank of Tests");
+        let account_number = Nuban::account_number_for("993", "123456789").unwrap();
+        let account = OwnedNuban::new("993".to_string(), account_number).unwrap();
+        assert_eq!(account.bank_name(), "Bank of Tests");
+
+        drop(registered);
+        assert_eq!(account.bank_name(), "993");
+        assert_eq!(account.try_bank_name(), None);
+    }
+
+    #[test]
+    fn test_owned_nuban_rejects_invalid() {
+        let err = OwnedNuban::new("058".to_string(), "0152792741".to_string()).unwrap_err();
+        assert_eq!(
+            err,
+            Error::account_number("0152792741", AccountError::CheckDigitMismatch)
+        );
+    }
+
+    #[cfg(all(feature = "logging", feature = "std"))]
+    #[test]
+    fn test_logs_check_digit_mismatch() {
+        use std::sync::Mutex;
+
+        struct Capture(Mutex<Vec<String>>);
+
+        impl log::Log for Capture {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        assert!(Nuban::new("058", "0152792741").is_err());
+        let messages = LOGGER.0.lock().unwrap();
+        assert!(messages.iter().any(|m| m == "bank code 058 recognized"));
+        assert!(messages
+            .iter()
+            .any(|m| m == "check digit expected 0, got 1"));
+    }
+
+    #[test]
+    fn test_copy() {
+        fn bank_code(nuban: Nuban) -> String {
+            nuban.bank_code().into()
+        }
+        fn account_number(nuban: Nuban) -> String {
+            nuban.account_number().into()
+        }
+
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(bank_code(account), "058");
+        assert_eq!(account_number(account), "0152792740");
+        assert_eq!(account.check_digit(), "0");
+    }
+
+    #[test]
+    fn test_eq_ignores_derived_fields() {
+        let built = Nuban::new("058", "0152792740").unwrap();
+        let parsed = Nuban::parse("0580152792740").unwrap();
+        assert_eq!(built, parsed);
+        assert_eq!(Nuban("058", "0152792740", ""), built);
+        assert_ne!(built, Nuban::new("057", "2209514399").unwrap());
+    }
+
+    #[test]
+    fn test_is_transposition_of() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert!(account.is_transposition_of("1052792740"));
+        assert!(account.is_transposition_of("0152792704"));
+        assert!(!account.is_transposition_of("0152792740"));
+        assert!(!account.is_transposition_of("0152792711"));
+        assert!(!account.is_transposition_of("015279274"));
+        assert!(!account.is_transposition_of("5102792740"));
+    }
+
+    #[test]
+    fn test_verify_against() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(
+            account.verify_against("058", "0152792740"),
+            VerifyResult::Match
+        );
+        assert_eq!(
+            account.verify_against("057", "2209514399"),
+            VerifyResult::BankMismatch
+        );
+        assert_eq!(
+            account.verify_against("058", "0000000018"),
+            VerifyResult::AccountMismatch
+        );
+        assert_eq!(
+            account.verify_against("058", "0152792741"),
+            VerifyResult::SubmittedInvalid
+        );
+        assert_eq!(
+            account.verify_against("000", "0152792740"),
+            VerifyResult::SubmittedInvalid
+        );
+    }
+
+    #[test]
+    fn test_eq_owned() {
+        let borrowed = Nuban::new("058", "0152792740").unwrap();
+        let owned = OwnedNuban::new("058".into(), "0152792740".into()).unwrap();
+        assert_eq!(borrowed, owned);
+        assert_eq!(owned, borrowed);
+        assert_eq!(borrowed.to_owned(), borrowed);
+
+        let other = Nuban::new("057", "2209514399").unwrap();
+        assert_ne!(other, owned);
+        assert_ne!(owned, other);
+    }
+
+    #[test]
+    fn test_same_account() {
+        let owned = OwnedNuban::new("058".into(), "0152792740".into()).unwrap();
+        assert!(Nuban::new("058", "0152792740")
+            .unwrap()
+            .same_account(&owned));
+        assert!(Nuban::parse("0580152792740").unwrap().same_account(&owned));
+        assert!(!Nuban::new("058", "0000000018")
+            .unwrap()
+            .same_account(&owned));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let accounts = [
+            Nuban::new("058", "0152792740").unwrap(),
+            Nuban::new("058", "0000000018").unwrap(),
+            Nuban::new("057", "2209514399").unwrap(),
+            Nuban::new("035", "2209514399").unwrap(),
+            Nuban::new("090267", "2209514399").unwrap(),
+        ];
+        for a in accounts.iter() {
+            for b in accounts.iter() {
+                assert_eq!(a.ct_eq(b), a == b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.format_grouped(&[2, 4, 4], ' '), "01 5279 2740");
+        assert_eq!(account.format_grouped(&[3, 3, 4], '-'), "015-279-2740");
+        assert_eq!(account.format_grouped(&[10], ' '), "0152792740");
+        assert_eq!(account.format_grouped(&[2, 4], ' '), "0152792740");
+        assert_eq!(account.format_grouped(&[2, 4, 4, 1], ' '), "0152792740");
+        assert_eq!(account.format_grouped(&[6, 0, 4], ' '), "0152792740");
+        assert_eq!(account.format_grouped(&[], ' '), "0152792740");
+        assert_eq!(account.format_grouped(&[usize::MAX, 11], ' '), "0152792740");
+    }
+
+    #[test]
+    fn test_mask() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.mask(), "058*******740");
+        assert_eq!(account.mask_with(4, '#'), "058######2740");
+        assert_eq!(account.mask_with(0, '*'), "058**********");
+        assert_eq!(account.mask_with(42, '*'), "0580152792740");
+    }
+
+    #[test]
+    fn test_phone_derived() {
+        let account = Nuban::new("120001", "8031234567").unwrap();
+        assert!(account.is_phone_derived());
+        assert_eq!(account.as_phone_number().as_deref(), Some("08031234567"));
+
+        let account = Nuban::new("120001", "2001234567").unwrap();
+        assert!(!account.is_phone_derived());
+        assert_eq!(account.as_phone_number(), None);
+
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert!(!account.is_phone_derived());
+        assert_eq!(account.as_phone_number(), None);
+        let account_number = Nuban::account_number_for("058", "803123456").unwrap();
+        let account = Nuban::new("058", &account_number).unwrap();
+        assert_eq!(account.as_phone_number(), None);
+    }
+
+    #[test]
+    fn test_log_safe() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.log_safe(), "Guaranty Trust Bank: 015*****40");
+        assert!(!account.log_safe().contains("27927"));
+    }
+
+    #[test]
+    fn test_display() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(format!("{}", account), "0580152792740");
+    }
+
+    #[test]
+    fn test_to_compact_string() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.to_compact_string(), "0580152792740");
+        assert_eq!(account.to_compact_string(), account.to_string());
+    }
+
+    #[test]
+    fn test_qr_payload() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let payload = account.to_qr_payload();
+        assert_eq!(payload, "NUBAN|058|0152792740|Guaranty Trust Bank");
+        assert_eq!(Nuban::from_qr_payload(&payload), Ok(account));
+
+        for payload in [
+            "NUBAN|058|0152792740",
+            "NUBAN|058|0152792740|Guaranty Trust Bank|extra",
+            "IBAN|058|0152792740|Guaranty Trust Bank",
+            "",
+        ] {
+            assert!(matches!(
+                Nuban::from_qr_payload(payload),
+                Err(Error::InvalidRecord(_))
+            ));
+        }
+        assert_eq!(
+            Nuban::from_qr_payload("NUBAN|058|0152792741|Guaranty Trust Bank")
+                .unwrap_err()
+                .reason(),
+            Some(AccountError::CheckDigitMismatch)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unknown_bank_is_named_by_code() {
+        let registry = BankRegistry::new().with_bank("991", "Bank of Tests");
+        let account_number = format!(
+            "123456789{}",
+            Nuban::calculate_check_digit(b"991", b"123456789")
+        );
+        let account = Nuban::new_with_registry(&registry, "991", &account_number).unwrap();
+        assert_eq!(account.bank_name(), "991");
+        assert!(account.to_qr_payload().ends_with("|991"));
+        assert!(account.summary().starts_with("Bank: 991 (991)\n"));
+        assert!(account.log_safe().starts_with("991: "));
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Nuban::new("058", "0152792740").unwrap().summary();
+        let lines: Vec<_> = summary.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Bank: Guaranty Trust Bank (058)",
+                "Account: 0152792740",
+                "Check digit: 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account, "0580152792740");
+        assert_eq!("0580152792740", account);
+        assert!(account == *"0580152792740");
+        assert_ne!(account, "0580152792741");
+        assert_ne!(account, "058015279274");
+        assert_ne!("0570152792740", account);
+    }
+
+    #[test]
+    fn test_returns_false_for_invalid_account() {
+        let account = Nuban::new("058", "0982736625");
+        assert!(account.is_err());
+    }
+
+    #[test]
+    fn test_returns_true_for_valid_account() {
+        let account = Nuban::new("058", "0152792740");
+        assert!(account.is_ok());
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Nuban::is_valid("058", "0152792740"));
+        assert!(Nuban::is_valid("090267", "2001234567"));
+        assert!(!Nuban::is_valid("058", "0152792741"));
+        assert!(!Nuban::is_valid("058", "015279274"));
+        assert!(!Nuban::is_valid("058", "015279274a"));
+        assert!(!Nuban::is_valid("000", "0152792740"));
+    }
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("058", "0152792740", Validity::Valid),
+            ("000", "0152792740", Validity::UnknownBank),
+            ("058", "015279274", Validity::WrongLength),
+            ("058", "01527927a0", Validity::NonNumeric),
+            ("058", "0152792741", Validity::ChecksumFailed),
+        ];
+        for (bank_code, account_number, validity) in cases.iter() {
+            assert_eq!(Nuban::classify(bank_code, account_number), *validity);
+        }
+    }
+
+    #[allow(deprecated)]
+    #[test]
+    fn test_is_valid_account() {
+        assert!(Nuban::is_valid_account("058", "0152792740"));
+        assert!(!Nuban::is_valid_account("058", "0152792741"));
+        assert!(!Nuban::is_valid_account("000", "0152792740"));
+    }
+
+    #[test]
+    fn test_rejects_non_digit_account_number() {
+        let inputs = [
+            "01527A2740",
+            "0152 92740",
+            "015279274 ",
+            "\u{660}15279274", // Arabic-Indic digit zero
+            "0\u{660}1527927",
+        ];
+        for input in inputs.iter() {
+            assert_eq!(
+                Nuban::new("058", input),
+                Err(Error::account_number(input, AccountError::NonDigit))
+            );
+            assert!(Nuban::bank_matches(input).is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_collecting() {
+        let errors = Nuban::validate_collecting("000", "0152792").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                Error::bank_code("000"),
+                Error::account_number("0152792", AccountError::WrongLength),
+            ]
+        );
+
+        let errors = Nuban::validate_collecting("000", "01A2").unwrap_err();
+        assert_eq!(errors.len(), 3);
+
+        let errors = Nuban::validate_collecting("058", "0152792741").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![Error::account_number(
+                "0152792741",
+                AccountError::CheckDigitMismatch
+            )]
+        );
+
+        let account = Nuban::validate_collecting("058", "0152792740");
+        assert_eq!(account, Ok(Nuban("058", "0152792740", "0")));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let inputs: [(&str, &str); 8] = [
+            ("058", "0152792740"),
+            ("058", "0152792741"),
+            ("058", "015279274"),
+            ("058", "01A"),
+            ("058", "015279274A"),
+            ("000", "0152792740"),
+            ("090267", "2001234567"),
+            ("05A", "0152792740"),
+        ];
+        for (bank_code, account_number) in inputs.iter() {
+            assert_eq!(
+                Nuban::from_bytes(bank_code.as_bytes(), account_number.as_bytes()),
+                Nuban::new(bank_code, account_number)
+            );
+        }
+        assert_eq!(
+            Nuban::from_bytes(b"058", b"01527\xff2740"),
+            Err(Error::account_number(
+                "01527\u{fffd}2740",
+                AccountError::NonDigit
+            ))
+        );
+    }
+
+    #[test]
+    fn test_try_from_tuple() {
+        use core::convert::{TryFrom, TryInto};
+
+        let account: Nuban = ("058", "0152792740").try_into().unwrap();
+        assert_eq!(account, Nuban("058", "0152792740", "0"));
+
+        let accounts = vec![("058", "0152792740"), ("057", "2209514399")]
+            .into_iter()
+            .map(Nuban::try_from)
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(accounts.unwrap().len(), 2);
+
+        let accounts = vec![("058", "0152792740"), ("000", "0152792740"), ("058", "1")]
+            .into_iter()
+            .map(Nuban::try_from)
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(accounts, Err(Error::bank_code("000")));
+    }
+
+    #[test]
+    fn test_validated_accounts() {
+        let pairs = [
+            ("058", "0152792741"),
+            ("058", "0152792740"),
+            ("000", "0152792740"),
+        ];
+        let results: Vec<_> = ValidatedAccounts::new(pairs.iter().copied()).collect();
+        assert_eq!(results, Nuban::validate_many(pairs.iter().copied()));
+
+        let mut seen = 0;
+        let first_valid = ValidatedAccounts::new(pairs.iter().copied().inspect(|_| seen += 1))
+            .find_map(Result::ok);
+        assert_eq!(first_valid, Some(Nuban("058", "0152792740", "0")));
+        assert_eq!(seen, 2);
+    }
+
+    #[test]
+    fn test_validate_many() {
+        let results = Nuban::validate_many(vec![
+            ("058", "0152792740"),
+            ("058", "0152792741"),
+            ("000", "0152792740"),
+            ("057", "2209514399"),
+        ]);
+        assert_eq!(
+            results,
+            vec![
+                Ok(Nuban("058", "0152792740", "0")),
+                Err(Error::account_number(
+                    "0152792741",
+                    AccountError::CheckDigitMismatch
+                )),
+                Err(Error::bank_code("000")),
+                Ok(Nuban("057", "2209514399", "9")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let account = Nuban::parse("0580152792740");
+        assert_eq!(account.unwrap(), Nuban("058", "0152792740", "0"));
+    }
+
+    #[test]
+    fn test_parse_wrong_length() {
+        assert_eq!(
+            Nuban::parse("058015279274"),
+            Err(Error::account_number(
+                "058015279274",
+                AccountError::WrongLength
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_non_digits() {
+        assert_eq!(
+            Nuban::parse("0580152A92740"),
+            Err(Error::account_number("0152A92740", AccountError::NonDigit))
+        );
+        assert_eq!(Nuban::parse("05B0152792740"), Err(Error::bank_code("05B")));
+        assert_eq!(
+            Nuban::parse("05\u{e9}152792740"),
+            Err(Error::bank_code("05\u{e9}152792740"))
+        );
+    }
+
+    #[test]
+    fn test_error_reasons() {
+        let err = Nuban::new("058", "015279274").unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidAccountNumber {
+                value: "015279274".to_string(),
+                reason: AccountError::WrongLength,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            r#"invalid account number "015279274": wrong length"#
+        );
+
+        let err = Nuban::new("058", "0152792741").unwrap_err();
+        assert_eq!(
+            err,
+            Error::InvalidAccountNumber {
+                value: "0152792741".to_string(),
+                reason: AccountError::CheckDigitMismatch,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            r#"invalid account number "0152792741": check digit mismatch"#
+        );
+
+        let err = Nuban::new("000", "0152792740").unwrap_err();
+        assert_eq!(err, Error::InvalidBankCode("000".to_string()));
+        assert_eq!(err.to_string(), r#"invalid bank code "000""#);
+    }
+
+    #[test]
+    fn test_error_reason_per_failure_mode() {
+        let cases = [
+            ("015279274", AccountError::WrongLength),
+            ("01527927400", AccountError::WrongLength),
+            ("", AccountError::WrongLength),
+            ("01527927A0", AccountError::NonDigit),
+            ("0152-92740", AccountError::NonDigit),
+            ("0152792741", AccountError::CheckDigitMismatch),
+            ("0152792749", AccountError::CheckDigitMismatch),
+        ];
+        for (account_number, reason) in cases.iter() {
+            let err = Nuban::new("058", account_number).unwrap_err();
+            assert_eq!(err.reason(), Some(*reason), "{}", account_number);
+        }
+        assert_eq!(Nuban::new("000", "015279274").unwrap_err().reason(), None);
+    }
+
+    #[test]
+    fn test_validate_checksum_only() {
+        let account_number = Nuban::account_number_for("058", "123456789").unwrap();
+        assert!(!Nuban::is_valid_bank("900"));
+        let check_digit = Nuban::calculate_check_digit(b"900", b"123456789");
+        let account_number_900 = format!("123456789{}", check_digit);
+        assert_eq!(
+            Nuban::validate_checksum_only("900", &account_number_900),
+            Ok(())
+        );
+        assert_eq!(
+            Nuban::validate_checksum_only("058", &account_number),
+            Ok(())
+        );
+        assert_eq!(
+            Nuban::validate_checksum_only("058", "0152792741"),
+            Err(Error::account_number(
+                "0152792741",
+                AccountError::CheckDigitMismatch
+            ))
+        );
+        assert_eq!(
+            Nuban::validate_checksum_only("90a", "0152792740"),
+            Err(Error::bank_code("90a"))
+        );
+        assert!(Nuban::validate_checksum_only("900", "123456789").is_err());
+    }
+
+    #[test]
+    fn test_validate_field() {
+        struct Transfer {
+            bank_code: &'static str,
+            account_number: &'static str,
+        }
+
+        impl Transfer {
+            fn validate(&self) -> Result<(), Error> {
+                Nuban::validate_field(self.bank_code, self.account_number)
+            }
+        }
+
+        let transfer = Transfer {
+            bank_code: "058",
+            account_number: "0152792740",
+        };
+        assert_eq!(transfer.validate(), Ok(()));
+
+        let transfer = Transfer {
+            bank_code: "058",
+            account_number: "0152792741",
+        };
+        assert_eq!(
+            transfer.validate(),
+            Err(Error::account_number(
+                "0152792741",
+                AccountError::CheckDigitMismatch
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let results = Nuban::parse_many(" 0580152792740\n\n0580152792741\n");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 1);
+        assert_eq!(
+            results[0].1.as_ref().unwrap().account_number(),
+            "0152792740"
+        );
+        assert_eq!(results[1].0, 3);
+        assert_eq!(
+            results[1].1,
+            Err(Error::account_number(
+                "0152792741",
+                AccountError::CheckDigitMismatch
+            ))
+        );
+    }
+
+    #[test]
+    fn test_error_predicates() {
+        let err = Error::bank_code("000");
+        assert!(err.is_bank_error());
+        assert!(!err.is_account_error());
+
+        for reason in [
+            AccountError::WrongLength,
+            AccountError::NonDigit,
+            AccountError::CheckDigitMismatch,
+            AccountError::PlaceholderSerial,
+        ] {
+            let err = Error::account_number("0152792741", reason);
+            assert!(err.is_account_error());
+            assert!(!err.is_bank_error());
+        }
+
+        let err = Error::InvalidRecord("expected 2 columns".into());
+        assert!(!err.is_bank_error());
+        assert!(!err.is_account_error());
+    }
+
+    #[test]
+    fn test_validate_str() {
+        assert_eq!(Nuban::validate_str("0580152792740"), Ok(()));
+        assert_eq!(
+            Nuban::validate_str("0580152792741"),
+            Err(Error::account_number(
+                "0152792741",
+                AccountError::CheckDigitMismatch
+            ))
+        );
+        assert_eq!(
+            Nuban::validate_str("058"),
+            Err(Error::account_number("058", AccountError::WrongLength))
+        );
+        assert!(Nuban::validate_str("").is_err());
+    }
+
+    #[test]
+    fn test_calculate_check_digit() {
+        let correct_check_digit = Nuban::calculate_check_digit(b"058", b"0152792740");
+        assert_eq!(correct_check_digit, 0);
+    }
+
+    #[test]
+    fn test_validate_macro() {
+        let account = validate!("058", "0152792740");
+        assert_eq!(account, Nuban::new("058", "0152792740").unwrap());
+        let account = crate::validate!("090267", "2001234567");
+        assert_eq!(account.check_digit(), "7");
+    }
+
+    #[test]
+    fn test_spec() {
+        assert!(!Nuban::spec_version().is_empty());
+        assert!(Nuban::spec_url().starts_with("https://"));
+        assert!(Nuban::spec_url().contains("cbn.gov.ng"));
+    }
+
+    #[test]
+    fn test_check_digit_weights() {
+        let weights = Nuban::check_digit_weights();
+        assert_eq!(weights.len(), 12);
+        assert_eq!(weights, &[3, 7, 3, 3, 7, 3, 3, 7, 3, 3, 7, 3]);
+    }
+
+    #[test]
+    fn test_calculate_check_digit_generic() {
+        for (bank, serial) in [
+            ("058", "015279274"),
+            ("057", "220951439"),
+            ("011", "000000001"),
+        ] {
+            let digits = format!("{}{}", bank, serial);
+            assert_eq!(
+                Nuban::calculate_check_digit_generic(digits.as_bytes()),
+                Nuban::calculate_check_digit(bank.as_bytes(), serial.as_bytes())
+            );
+        }
+        assert_eq!(Nuban::calculate_check_digit_generic(b""), 0);
+        assert_eq!(Nuban::calculate_check_digit_generic(b"1"), 7);
+    }
+
+    #[test]
+    fn test_const_check_digit() {
+        const CHECK_DIGIT: u32 = Nuban::calculate_check_digit(b"057", b"220951439");
+        assert_eq!(CHECK_DIGIT, 9);
+        assert_eq!(Ok(CHECK_DIGIT), Nuban::check_digit_for("057", "220951439"));
+    }
+
+    #[test]
+    fn test_checksum_remainder() {
+        assert_eq!(Nuban::checksum_remainder("058", "015279274"), Ok(0));
+        assert_eq!(Nuban::check_digit_for("058", "015279274"), Ok(0));
+        assert_eq!(Nuban::checksum_remainder("057", "220951439"), Ok(1));
+        assert_eq!(Nuban::check_digit_for("057", "220951439"), Ok(9));
+        assert!(Nuban::checksum_remainder("058", "01527927").is_err());
+    }
+
+    #[test]
+    fn test_weighted_digits() {
+        let weighted = Nuban::weighted_digits("057", "220951439").unwrap();
+        assert_eq!(weighted.len(), 12);
+        assert_eq!(weighted[1], (5, 7, 35));
+        let check_sum: u32 = weighted.iter().map(|&(_, _, product)| product).sum();
+        assert_eq!(
+            Ok(check_sum % 10),
+            Nuban::checksum_remainder("057", "220951439")
+        );
+        assert!(Nuban::weighted_digits("000", "220951439").is_err());
+    }
+
+    #[test]
+    fn test_valid_account_space() {
+        assert_eq!(Nuban::valid_account_space("058"), Ok(1_000_000_000));
+        assert_eq!(Nuban::valid_account_space("090267"), Ok(10_000_000_000));
+        assert_eq!(
+            Nuban::valid_account_space("000"),
+            Err(Error::bank_code("000"))
+        );
+    }
+
+    #[test]
+    fn test_check_digit_for() {
+        assert_eq!(Nuban::check_digit_for("058", "015279274"), Ok(0));
+        assert_eq!(Nuban::check_digit_for("057", "220951439"), Ok(9));
+        assert_eq!(
+            Nuban::check_digit_for("000", "015279274"),
+            Err(Error::bank_code("000"))
+        );
+        assert_eq!(
+            Nuban::check_digit_for("058", "01527927"),
+            Err(Error::account_number("01527927", AccountError::WrongLength))
+        );
+        assert_eq!(
+            Nuban::check_digit_for("058", "01527927A"),
+            Err(Error::account_number("01527927A", AccountError::NonDigit))
+        );
+    }
+
+    #[test]
+    fn test_account_number_for() {
+        let account_number = Nuban::account_number_for("058", "015279274").unwrap();
+        assert_eq!(account_number, "0152792740");
+
+        for (code, _) in Nuban::all_banks().filter(|(code, _)| code.len() == 3) {
+            let account_number = Nuban::account_number_for(code, "123456789").unwrap();
+            assert!(Nuban::new(code, &account_number).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_from_serial() {
+        let account = Nuban::from_serial("058", "015279274").unwrap();
+        assert_eq!(account.check_digit(), "0");
+        assert_eq!(
+            Nuban::new("058", account.account_number()),
+            Ok(account.as_ref())
+        );
+
+        for serial in ["123456789", "000000001", "999999999"] {
+            let account = Nuban::from_serial("057", serial).unwrap();
+            assert_eq!(account.serial(), serial);
+            assert!(Nuban::new("057", account.account_number()).is_ok());
+        }
+        assert_eq!(
+            Nuban::from_serial("000", "015279274"),
+            Err(Error::bank_code("000"))
+        );
+        assert_eq!(
+            Nuban::from_serial("058", "0152792740"),
+            Err(Error::account_number(
+                "0152792740",
+                AccountError::WrongLength
+            ))
+        );
+    }
+
+    #[test]
+    fn test_suggest_correction() {
+        let account = Nuban::suggest_correction("058", "0152792741").unwrap();
+        assert_eq!(account.account_number(), "0152792740");
+        assert_eq!(Nuban::suggest_correction("058", "0152792740"), None);
+        assert_eq!(Nuban::suggest_correction("058", "015279274"), None);
+        assert_eq!(Nuban::suggest_correction("000", "0152792741"), None);
+    }
+
+    #[cfg(all(feature = "rand", feature = "std"))]
+    #[test]
+    fn test_random_valid() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let account = Nuban::random_valid("058").unwrap();
+        assert!(Nuban::new(account.bank_code(), account.account_number()).is_ok());
+
+        let first = Nuban::random_valid_with("057", &mut StdRng::seed_from_u64(42)).unwrap();
+        let second = Nuban::random_valid_with("057", &mut StdRng::seed_from_u64(42)).unwrap();
+        assert_eq!(first, second);
+        assert!(Nuban::new(first.bank_code(), first.account_number()).is_ok());
+
+        assert_eq!(Nuban::random_valid("000"), Err(Error::bank_code("000")));
+    }
+
+    #[test]
+    fn test_sequential_valid() {
+        let accounts = Nuban::sequential_valid("058", 15279274, 5).unwrap();
+        assert_eq!(accounts.len(), 5);
+        assert_eq!(accounts[0].account_number(), "0152792740");
+        for (i, account) in accounts.iter().enumerate() {
+            assert!(Nuban::new("058", account.account_number()).is_ok());
+            assert_eq!(account.serial(), format!("{:09}", 15279274 + i));
+        }
+
+        assert_eq!(
+            Nuban::sequential_valid("058", 999_999_998, 5)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            Nuban::sequential_valid("000", 0, 5),
+            Err(Error::bank_code("000"))
+        );
+    }
+
+    #[test]
+    fn test_valid_accounts_for_serial() {
+        let accounts = Nuban::valid_accounts_for_serial("015279274");
+        let banks = Nuban::all_banks().filter(|(code, _)| has_check_digit(code));
+        assert_eq!(accounts.len(), banks.count());
+        for (code, account) in accounts.iter() {
+            assert_eq!(account.bank_code(), *code);
+            assert_eq!(account.serial(), "015279274");
+            assert!(Nuban::new(code, account.account_number()).is_ok());
+        }
+        assert!(accounts.contains(&("058", Nuban("058", "0152792740", "0").to_owned())));
+        assert!(Nuban::valid_accounts_for_serial("01527927").is_empty());
+    }
+
+    #[test]
+    fn test_get_bank_name() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.bank_name(), String::from("Guaranty Trust Bank"));
+    }
+
+    /// Unregisters a bank from the global registry when dropped, so tests
+    /// running in parallel never see it once its test is over, pass or fail.
+    #[cfg(feature = "std")]
+    struct RegisteredBank(&'static str);
+
+    #[cfg(feature = "std")]
+    impl RegisteredBank {
+        fn new(code: &'static str, name: &'static str) -> Self {
+            Self::with_category(code, name, BankCategory::Unknown)
+        }
+
+        fn with_category(code: &'static str, name: &'static str, category: BankCategory) -> Self {
+            Nuban::register_bank_with_category(code, name, category);
+            RegisteredBank(code)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Drop for RegisteredBank {
+        fn drop(&mut self) {
+            Nuban::unregister_bank(self.0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_register_bank() {
+        assert!(!Nuban::is_valid_bank("999"));
+        let _registered = RegisteredBank::new("999", "Bank of Tests");
+        assert!(Nuban::is_valid_bank("999"));
+
+        let account_number = Nuban::account_number_for("999", "123456789").unwrap();
+        let account = Nuban::new("999", &account_number).unwrap();
+        assert_eq!(account.bank_name(), "Bank of Tests");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_register_bank_overrides_built_in() {
+        let registered = RegisteredBank::new("215", "Unity Bank Plc");
+        let account_number = Nuban::account_number_for("215", "123456789").unwrap();
+        let account = Nuban::new("215", &account_number).unwrap();
+        assert_eq!(account.bank_name(), "Unity Bank Plc");
+        drop(registered);
+        assert_eq!(account.bank_name(), "Unity Bank");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_bank_name_or() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.bank_name_or("Unknown bank"), "Guaranty Trust Bank");
+
+        let _registered = RegisteredBank::new("992", "Bank of Tests");
+        let account_number = Nuban::account_number_for("992", "123456789").unwrap();
+        let account = Nuban::new("992", &account_number).unwrap();
+        assert_eq!(account.bank_name_or("Unknown bank"), "Bank of Tests");
+        Nuban::unregister_bank("992");
+        assert_eq!(account.bank_name_or("Unknown bank"), "Unknown bank");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_concurrent_registration() {
+        use std::thread;
+
+        const CODES: [&str; 4] = ["980", "981", "982", "983"];
+        let _registered: Vec<_> = CODES.iter().map(|&code| RegisteredBank(code)).collect();
+        let writers = CODES.iter().map(|&code| {
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    Nuban::register_bank(code, "Bank of Threads");
+                    Nuban::unregister_bank(code);
+                }
+                Nuban::register_bank(code, "Bank of Threads");
+            })
+        });
+        let readers = (0..4).map(|_| {
+            thread::spawn(|| {
+                for _ in 0..100 {
+                    assert_eq!(Nuban::lookup_bank_name("058"), Some("Guaranty Trust Bank"));
+                    for code in CODES.iter() {
+                        if let Some(name) = Nuban::lookup_bank_name(code) {
+                            assert_eq!(name, "Bank of Threads");
+                        }
+                    }
+                }
+            })
+        });
+        let handles: Vec<_> = writers.chain(readers).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for code in CODES.iter() {
+            assert!(Nuban::is_valid_bank(code));
+            assert_eq!(Nuban::lookup_bank_name(code), Some("Bank of Threads"));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_bank_name() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.try_bank_name(), Some("Guaranty Trust Bank"));
+
+        let _registered = RegisteredBank::new("997", "Bank of Tests");
+        let account_number = Nuban::account_number_for("997", "123456789").unwrap();
+        let account = Nuban::new("997", &account_number).unwrap();
+        assert_eq!(account.try_bank_name(), Some("Bank of Tests"));
+
+        Nuban::unregister_bank("997");
+        assert!(!Nuban::is_valid_bank("997"));
+        assert_eq!(account.try_bank_name(), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_new_with_registry() {
+        let registry = BankRegistry::new().with_bank("058", "GTBank");
+        assert!(Nuban::new_with_registry(&registry, "058", "0152792740").is_ok());
+        assert_eq!(
+            Nuban::new_with_registry(&registry, "057", "2209514399"),
+            Err(Error::bank_code("057"))
+        );
+        assert_eq!(registry.bank_name("058"), Some("GTBank"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_non_digit_bank_code_does_not_panic() {
+        let registry = BankRegistry::new().with_bank("ab1", "Bank of Letters");
+        assert!(Nuban::new_with_registry(&registry, "ab1", "0152792740").is_err());
+        assert!(Nuban::check_digit_for("ab1", "015279274").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_bank_name_falls_back_to_code() {
+        let registry = BankRegistry::new().with_bank("991", "Bank of Tests");
+        let account_number = Nuban::account_number_for("058", "123456789").unwrap();
+        let check_digit = Nuban::calculate_check_digit(b"991", b"123456789");
+        let account_number_991 = format!("123456789{}", check_digit);
+        let account = Nuban::new_with_registry(&registry, "991", &account_number_991).unwrap();
+        assert_eq!(account.try_bank_name(), None);
+        assert_eq!(account.bank_name(), "991");
+        assert_eq!(account.bank().name, "991");
+        assert_eq!(
+            Nuban::new("058", &account_number).unwrap().bank_name(),
+            "Guaranty Trust Bank"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_registry_with_defaults() {
+        let registry = BankRegistry::new()
+            .with_defaults()
+            .with_bank("998", "Bank of Tests");
+        assert!(Nuban::new_with_registry(&registry, "057", "2209514399").is_ok());
+        assert_eq!(registry.bank_name("998"), Some("Bank of Tests"));
+        assert!(!Nuban::is_valid_bank("998"));
+    }
+
+    #[test]
+    fn test_cbn_nuban() {
+        assert_eq!(CbnNuban.check_digit("058", "015279274"), Some(0));
+        assert_eq!(CbnNuban.check_digit("090267", "200123456"), None);
+        assert_eq!(CbnNuban.check_digit("058", "1"), None);
+        assert_eq!(CbnNuban.check_digit("058", "0152792740"), None);
+        assert_eq!(CbnNuban.check_digit("058", "abcdefghi"), None);
+        assert_eq!(CbnNuban.check_digit(" !1", "015279274"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_registry_with_algorithm() {
+        #[derive(Debug)]
+        struct AlwaysZero;
+
+        impl CheckDigitAlgorithm for AlwaysZero {
+            fn check_digit(&self, _: &str, _: &str) -> Option<u32> {
+                Some(0)
+            }
+        }
+
+        let registry = BankRegistry::new()
+            .with_defaults()
+            .with_bank("993", "Bank of Zeros")
+            .with_algorithm("993", AlwaysZero);
+        let account = Nuban::new_with_registry(&registry, "993", "1234567890").unwrap();
+        assert!(!account.check_digit_matches());
+        assert_eq!(
+            Nuban::new_with_registry(&registry, "993", "1234567891"),
+            Err(Error::account_number(
+                "1234567891",
+                AccountError::CheckDigitMismatch
+            ))
+        );
+        assert!(Nuban::new_with_registry(&registry, "058", "0152792740").is_ok());
+        assert!(Nuban::new_with_registry(&registry, "058", "0152792741").is_err());
+        assert_eq!(registry.clone(), registry);
+    }
+
+    #[test]
+    fn test_active_bank_codes() {
+        let active = [
+            ("044", "Access Bank"),
+            ("076", "Polaris Bank"),
+            ("082", "Keystone Bank"),
+            ("101", "Providus Bank"),
+            ("301", "Jaiz Bank"),
+        ];
+        for (code, name) in active.iter() {
+            let account_number = Nuban::account_number_for(code, "123456789").unwrap();
+            assert_eq!(
+                Nuban::new(code, &account_number).unwrap().bank_name(),
+                *name
+            );
+        }
+        assert!(!Nuban::is_valid_bank("056")); // Oceanic Bank, merged into Ecobank
+    }
+
+    #[test]
+    fn test_long_bank_code() {
+        let account = Nuban::new("090267", "2001234567").unwrap();
+        assert_eq!(account.bank_name(), "Kuda Microfinance Bank");
+        assert_eq!(account.check_digit(), "7");
+        assert!(Nuban::new("090267", "2001234568").is_ok());
+        assert_eq!(
+            Nuban::new("090267", "200123456"),
+            Err(Error::account_number(
+                "200123456",
+                AccountError::WrongLength
+            ))
+        );
+        assert_eq!(
+            Nuban::new("090267", "20012345A7"),
+            Err(Error::account_number("20012345A7", AccountError::NonDigit))
+        );
+        assert_eq!(
+            Nuban::new("090268", "2001234567"),
+            Err(Error::bank_code("090268"))
+        );
+        assert_eq!(
+            Nuban::check_digit_for("090267", "200123456"),
+            Err(Error::bank_code("090267"))
+        );
+    }
+
+    #[test]
+    fn test_bank_category() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        assert_eq!(account.bank_category(), BankCategory::Commercial);
+        let account_number = Nuban::account_number_for("501", "123456789").unwrap();
+        let account = Nuban::new("501", &account_number).unwrap();
+        assert_eq!(account.bank_category(), BankCategory::Merchant);
+        let account = Nuban::new("120001", "8031234567").unwrap();
+        assert_eq!(account.bank_category(), BankCategory::PaymentService);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_registered_bank_category() {
+        let _microfinance = RegisteredBank::with_category(
+            "996",
+            "Test Microfinance Bank",
+            BankCategory::Microfinance,
+        );
+        let account_number = Nuban::account_number_for("996", "123456789").unwrap();
+        let account = Nuban::new("996", &account_number).unwrap();
+        assert_eq!(account.bank_category(), BankCategory::Microfinance);
+
+        let _registered = RegisteredBank::new("995", "Bank of Tests");
+        let account_number = Nuban::account_number_for("995", "123456789").unwrap();
+        let account = Nuban::new("995", &account_number).unwrap();
+        assert_eq!(account.bank_category(), BankCategory::Unknown);
+    }
+
+    #[test]
+    fn test_bank_names() {
+        assert_eq!(
+            Nuban::bank_names(&["058", "000", "057"]),
+            vec![Some("Guaranty Trust Bank"), None, Some("Zenith Bank")]
+        );
+        assert!(Nuban::bank_names(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_code_for_bank() {
+        assert_eq!(Nuban::code_for_bank("guaranty trust bank"), Some("058"));
+        assert_eq!(Nuban::code_for_bank("Zenith Bank"), Some("057"));
+        assert_eq!(Nuban::code_for_bank("  Wema Bank\n"), Some("035"));
+        assert_eq!(Nuban::code_for_bank("Bank of Nowhere"), None);
+    }
+
+    #[test]
+    fn test_resolve_bank_fuzzy() {
+        assert_eq!(Nuban::resolve_bank_fuzzy("GTB"), Some("058"));
+        assert_eq!(Nuban::resolve_bank_fuzzy("gt bank"), Some("058"));
+        assert_eq!(Nuban::resolve_bank_fuzzy("Guaranty  Trust"), Some("058"));
+        assert_eq!(Nuban::resolve_bank_fuzzy("UBA Plc"), Some("033"));
+        assert_eq!(Nuban::resolve_bank_fuzzy("zenith"), Some("057"));
+        assert_eq!(Nuban::resolve_bank_fuzzy("Bank of Nowhere"), None);
+        assert_eq!(Nuban::resolve_bank_fuzzy("bank"), None);
+    }
+
+    #[test]
+    fn test_bank_membership() {
+        assert_eq!(Nuban::bank_count(), BANKS.len());
+        assert!(Nuban::has_bank_code("035"));
+        assert!(!Nuban::has_bank_code("000"));
+        assert!(Nuban::has_bank_name("Wema Bank"));
+        assert!(Nuban::has_bank_name("wema bank"));
+        assert!(!Nuban::has_bank_name("Fake Bank"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_code_for_registered_bank() {
+        let _registered = RegisteredBank::new("994", "Registered Test Bank");
+        assert_eq!(Nuban::code_for_bank("registered test bank"), Some("994"));
+        assert!(Nuban::has_bank_name("Registered Test Bank"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_is_std_error() {
+        fn validate() -> Result<(), Box<dyn std::error::Error>> {
+            Nuban::new("058", "0152792741")?;
+            Ok(())
+        }
+        let err = validate().unwrap_err();
+        let expected = Error::account_number("0152792741", AccountError::CheckDigitMismatch);
+        assert_eq!(err.to_string(), expected.to_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_into_io_error() {
+        let errors = [
+            Error::bank_code("000"),
+            Error::account_number("0152792741", AccountError::CheckDigitMismatch),
+        ];
+        for err in errors.iter() {
+            let io_err = std::io::Error::from(err.clone());
+            assert_eq!(io_err.kind(), std::io::ErrorKind::InvalidData);
+            assert_eq!(io_err.to_string(), err.to_string());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let json = serde_json::to_string(&account).unwrap();
+        assert_eq!(json, r#"{"bank_code":"058","account_number":"0152792740"}"#);
+        assert_eq!(serde_json::from_str::<Nuban>(&json).unwrap(), account);
+
+        let err = Error::account_number("0152792741", AccountError::CheckDigitMismatch);
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(serde_json::from_str::<Error>(&json).unwrap(), err);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_invalid_account() {
+        let json = r#"{"bank_code":"058","account_number":"0152792741"}"#;
+        let err = serde_json::from_str::<Nuban>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid account number"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_banks_concurrent_init() {
+        let handles = (0..8)
+            .map(|_| std::thread::spawn(|| Nuban::banks() as *const _ as usize))
+            .collect::<Vec<_>>();
+        let maps = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        assert!(maps.iter().all(|&map| map == maps[0]));
+        assert_eq!(Nuban::banks().len(), BANKS.len());
+        assert_eq!(Nuban::banks().get("058"), Some(&"Guaranty Trust Bank"));
+    }
+
+    #[test]
+    fn test_all_banks() {
+        let mut banks = Nuban::all_banks();
+        assert_eq!(banks.next(), Some(("044", "Access Bank")));
+        assert_eq!(Nuban::all_banks().count(), BANKS.len());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn test_banks_as_json() {
+        let banks: Vec<serde_json::Value> = serde_json::from_str(&Nuban::banks_as_json()).unwrap();
+        assert_eq!(banks.len(), BANKS.len());
+        assert_eq!(
+            banks[0],
+            serde_json::json!({ "code": "044", "name": "Access Bank" })
+        );
+    }
+
+    #[test]
+    fn test_banks_sorted() {
+        let banks = Nuban::banks_sorted();
+        assert_eq!(banks[0], ("011", "First Bank"));
+        assert_eq!(banks.len(), BANKS.len());
+        assert!(banks.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_banks_with_prefix() {
+        let codes: Vec<_> = Nuban::banks_with_prefix("2")
+            .into_iter()
+            .map(|(code, _)| code)
+            .collect();
+        assert_eq!(codes, vec!["214", "221", "232", "215"]);
+
+        let banks = Nuban::banks_with_prefix("0");
+        assert!(banks.contains(&("058", "Guaranty Trust Bank")));
+        assert!(banks.contains(&("090267", "Kuda Microfinance Bank")));
+        assert!(banks.iter().all(|(code, _)| code.starts_with('0')));
+
+        assert_eq!(Nuban::banks_with_prefix("").len(), BANKS.len());
+        assert!(Nuban::banks_with_prefix("9").is_empty());
+    }
+
+    #[test]
+    fn test_candidate_banks() {
+        let candidates = Nuban::candidate_banks("0152792740");
+        assert!(candidates.contains(&"058"));
+        assert!(candidates.len() < BANKS.len());
+        assert!(candidates
+            .iter()
+            .all(|code| Nuban::new(code, "0152792740").is_ok()));
+        assert!(Nuban::candidate_banks("015279274").is_empty());
     }
 
     #[test]
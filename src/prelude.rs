@@ -0,0 +1,18 @@
+//! The types most users need, for glob importing.
+//!
+//! ```
+//! use nuban::prelude::*;
+//!
+//! let account = Nuban::new("058", "0152792740").unwrap();
+//! assert_eq!(account.bank_name(), "Guaranty Trust Bank");
+//!
+//! let err = Nuban::new("000", "0152792740").unwrap_err();
+//! assert_eq!(err, Error::InvalidBankCode("000".into()));
+//! ```
+
+#[cfg(feature = "std")]
+pub use crate::BankRegistry;
+pub use crate::{
+    resolve::{NameEnquiry, ResolveError},
+    AccountError, BankCategory, CheckDigitAlgorithm, Error, Nuban, OwnedNuban, Validity,
+};
@@ -0,0 +1,102 @@
+//! Name enquiry, i.e. resolving a validated account to its holder's name.
+//!
+//! A valid check digit only means an account number is well-formed; it
+//! doesn't mean the account exists. Confirming that needs a provider such
+//! as NIBSS, Paystack or Flutterwave. This crate doesn't ship an HTTP
+//! client, so providers implement `NameEnquiry` on top of whichever client
+//! the application already uses.
+
+use crate::Nuban;
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use core::fmt;
+
+pub trait NameEnquiry {
+    /// Returns the name of the account holder.
+    fn resolve(&self, nuban: &Nuban) -> Result<String, ResolveError>;
+}
+
+/// Any `Fn(&Nuban) -> Result<String, ResolveError>` is a `NameEnquiry`, so
+/// a provider can be a closure around an HTTP client.
+impl<F> NameEnquiry for F
+where
+    F: Fn(&Nuban) -> Result<String, ResolveError>,
+{
+    fn resolve(&self, nuban: &Nuban) -> Result<String, ResolveError> {
+        self(nuban)
+    }
+}
+
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub enum ResolveError {
+    /// The provider has no account for this NUBAN.
+    NotFound,
+    /// The provider could not be reached or returned an error.
+    Provider(String),
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::NotFound => write!(f, "account not found"),
+            ResolveError::Provider(reason) => write!(f, "name enquiry failed: {}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResolveError {}
+
+/// A `NameEnquiry` that answers from a fixed set of names, for tests.
+#[derive(Eq, Clone, Debug, Default, PartialEq)]
+pub struct MockNameEnquiry {
+    names: BTreeMap<String, String>,
+}
+
+impl MockNameEnquiry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_name(mut self, nuban: &Nuban, name: impl Into<String>) -> Self {
+        self.names.insert(nuban.to_string(), name.into());
+        self
+    }
+}
+
+impl NameEnquiry for MockNameEnquiry {
+    fn resolve(&self, nuban: &Nuban) -> Result<String, ResolveError> {
+        let name = self.names.get(&nuban.to_string());
+        name.cloned().ok_or(ResolveError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::prelude::v1::*;
+
+    #[test]
+    fn test_mock_name_enquiry() {
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let other = Nuban::new("057", "2209514399").unwrap();
+        let provider = MockNameEnquiry::new().with_name(&account, "JOHN DOE");
+
+        assert_eq!(provider.resolve(&account), Ok("JOHN DOE".to_string()));
+        assert_eq!(provider.resolve(&other), Err(ResolveError::NotFound));
+    }
+
+    #[test]
+    fn test_closure_name_enquiry() {
+        fn lookup(provider: &impl NameEnquiry, nuban: &Nuban) -> Result<String, ResolveError> {
+            provider.resolve(nuban)
+        }
+
+        let account = Nuban::new("058", "0152792740").unwrap();
+        let provider = |_: &Nuban| Err(ResolveError::Provider("timed out".to_string()));
+        let err = lookup(&provider, &account).unwrap_err();
+        assert_eq!(err.to_string(), "name enquiry failed: timed out");
+    }
+}
@@ -0,0 +1,39 @@
+//! `wasm-bindgen` bindings for validating NUBANs in the browser.
+//!
+//! These take and return owned values, since borrowed `Nuban`s can't cross
+//! the JS boundary.
+
+// `#[wasm_bindgen]` generates `unsafe` glue code on wasm targets.
+#![allow(unsafe_code)]
+
+use crate::Nuban;
+use alloc::string::{String, ToString};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn validate_nuban(bank_code: &str, account_number: &str) -> bool {
+    Nuban::new(bank_code, account_number).is_ok()
+}
+
+#[wasm_bindgen]
+pub fn bank_name_for(bank_code: &str) -> Option<String> {
+    Nuban::lookup_bank_name(bank_code).map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_nuban() {
+        assert!(validate_nuban("058", "0152792740"));
+        assert!(!validate_nuban("058", "0152792741"));
+        assert!(!validate_nuban("000", "0152792740"));
+    }
+
+    #[test]
+    fn test_bank_name_for() {
+        assert_eq!(bank_name_for("058").as_deref(), Some("Guaranty Trust Bank"));
+        assert_eq!(bank_name_for("000"), None);
+    }
+}